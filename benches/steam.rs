@@ -0,0 +1,106 @@
+//! Benchmarks Steam detection on a synthetic Steam directory with a large library, and a much
+//! larger `librarycache` (which also contains images of apps which are owned but not installed).
+//!
+//! Compare against another revision by running `cargo bench --bench steam -- --save-baseline main`
+//! on it first, then `cargo bench --bench steam -- --baseline main`.
+
+use std::{
+    fs::{create_dir_all, write},
+    path::Path,
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use lib_game_detector::{data::SupportedLaunchers, get_detector};
+
+const INSTALLED_APPS: u32 = 2_000;
+const CACHED_APPS: u32 = 10_000;
+
+/// Create a Steam directory at the given path, with the main library containing
+/// `INSTALLED_APPS` apps and the `librarycache` containing images of `CACHED_APPS` apps
+fn create_steam_dir(path_steam_dir: &Path) {
+    let path_steamapps = path_steam_dir.join("steamapps");
+    let path_library_cache = path_steam_dir.join("appcache/librarycache");
+    create_dir_all(&path_steamapps).unwrap();
+
+    write(
+        path_steamapps.join("libraryfolders.vdf"),
+        format!(
+            "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+            path_steam_dir.display()
+        ),
+    )
+    .unwrap();
+
+    for app_id in 0..CACHED_APPS {
+        if app_id < INSTALLED_APPS {
+            write(
+                path_steamapps.join(format!("appmanifest_{app_id}.acf")),
+                format!(
+                    "\"AppState\"\n{{\n\t\"appid\"\t\t\"{app_id}\"\n\t\"name\"\t\t\"Game {app_id}\"\n\t\"StateFlags\"\t\t\"4\"\n\t\"installdir\"\t\t\"Game {app_id}\"\n}}\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        // Newer structure, with a directory for each app, where some images are in
+        // sub-directories, e.g. for other languages
+        let path_app_dir = path_library_cache.join(app_id.to_string());
+        let path_hash_dir = path_app_dir.join(format!("{app_id:040x}"));
+        create_dir_all(&path_hash_dir).unwrap();
+
+        let mut file_names = vec![
+            String::from("header.jpg"),
+            String::from("library_hero.jpg"),
+            String::from("logo.png"),
+            format!("{:040x}.jpg", app_id + 1),
+        ];
+        let mut hash_dir_file_names = vec!["library_600x900_russian.jpg", "logo.png"];
+        if app_id % 4 == 0 {
+            hash_dir_file_names.push("library_600x900.jpg");
+        } else {
+            file_names.push(String::from("library_600x900.jpg"));
+        }
+
+        for file_name in file_names {
+            write(path_app_dir.join(file_name), []).unwrap();
+        }
+        for file_name in hash_dir_file_names {
+            write(path_hash_dir.join(file_name), []).unwrap();
+        }
+    }
+}
+
+fn steam_benchmark(c: &mut Criterion) {
+    let path_home = std::env::temp_dir().join(format!(
+        "lib_game_detector_bench_steam_{}",
+        std::process::id()
+    ));
+    let path_data = path_home.join(".local/share");
+    create_steam_dir(&path_data.join("Steam"));
+
+    // SAFETY: no other threads have been spawned yet
+    unsafe {
+        std::env::set_var("HOME", &path_home);
+        std::env::set_var("XDG_DATA_HOME", &path_data);
+    }
+
+    let detector = get_detector();
+    assert_eq!(
+        detector
+            .get_all_detected_games_from_specific_launcher(SupportedLaunchers::Steam)
+            .map(|games| games.len()),
+        Some(INSTALLED_APPS as usize)
+    );
+
+    let mut group = c.benchmark_group("steam");
+    group.sample_size(20);
+    group.bench_function("synthetic library", |b| {
+        b.iter(|| detector.get_all_detected_games_from_specific_launcher(SupportedLaunchers::Steam))
+    });
+    group.finish();
+
+    std::fs::remove_dir_all(&path_home).unwrap();
+}
+
+criterion_group!(benches, steam_benchmark);
+criterion_main!(benches);
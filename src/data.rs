@@ -43,6 +43,208 @@ pub struct Game {
     /// Game detection source.
     #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_debug"))]
     pub source: SupportedLaunchers,
+    /// Install variant of whatever runs the launch command, e.g. the flatpak version of Steam.
+    pub install_variant: InstallVariant,
+    /// Additional metadata about the game, for sources which provide it.
+    pub metadata: GameMetadata,
+}
+
+/// Additional metadata about a game. Fields are left empty if the source doesn't provide them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GameMetadata {
+    /// Developer(s) of the game, e.g. `Valve`.
+    pub developer: Option<String>,
+    /// Publisher(s) of the game.
+    pub publisher: Option<String>,
+    /// Release date, as a Unix timestamp in seconds.
+    pub release_date: Option<u64>,
+    /// Genres of the game, e.g. `Action`, `Indie`.
+    pub genres: Vec<String>,
+    /// Operating systems the game supports, e.g. `windows`, `linux`.
+    pub supported_os: Vec<String>,
+    /// Whether the game is ready to launch, or is being downloaded, updated etc.
+    pub install_state: Option<InstallState>,
+    /// Installed DLC of the game.
+    pub dlc: Vec<Dlc>,
+    /// Installed depots (sets of content files) of the game, for Steam games.
+    pub depots: Vec<Depot>,
+    /// Compatibility tool (e.g. Proton) used to run the game, if it doesn't run natively.
+    pub compat_tool: Option<CompatTool>,
+    /// Path to the executable run by the launcher, for games it launches directly.
+    pub path_executable: Option<PathBuf>,
+    /// Working directory the game is started in.
+    pub path_working_dir: Option<PathBuf>,
+    /// Path to a landscape cover image of the game, e.g. GOG's store banner.
+    pub path_cover: Option<PathBuf>,
+    /// Path to the game's logo.
+    pub path_logo: Option<PathBuf>,
+    /// Path to a background image of the game, e.g. shown behind its page in the launcher.
+    pub path_background: Option<PathBuf>,
+    /// Extra arguments the user has set to launch the game with.
+    pub launch_options: Option<String>,
+    /// Environment variables the user has set to launch the game with, as `(name, value)` pairs.
+    pub environment: Vec<(String, String)>,
+    /// Whether the game is launched with Feral GameMode.
+    pub use_game_mode: bool,
+    /// Whether the game is launched with the MangoHud overlay.
+    pub use_mangohud: bool,
+    /// Collections (or tags) the user has added the game to, e.g. `Shooters`. Favourites are given
+    /// by `is_favorite` instead.
+    pub collections: Vec<String>,
+    /// Whether the user has added the game to their favourites.
+    pub is_favorite: bool,
+    /// Whether the user has hidden the game in the launcher.
+    pub is_hidden: bool,
+    /// Whether the game is launched in VR.
+    pub is_vr: bool,
+    /// When the game was last played, as a Unix timestamp in seconds.
+    pub last_played: Option<u64>,
+    /// Total time the game has been played for, in minutes.
+    pub playtime: Option<u64>,
+    /// Screenshots the user has taken of the game, from oldest to newest.
+    pub screenshots: Vec<Screenshot>,
+    /// How many of the game's achievements the user has unlocked.
+    pub achievements: Option<Achievements>,
+    /// SteamID of the Steam account the game belongs to. For Steam games, which are shared by all
+    /// accounts, this is the account which played it most recently.
+    pub steam_id: Option<u64>,
+}
+
+/// Screenshot of a game taken by the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Screenshot {
+    /// Path to the full size image.
+    pub path: PathBuf,
+    /// Path to a smaller version of the image, if one exists.
+    pub path_thumbnail: Option<PathBuf>,
+}
+
+/// Achievement progress of a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Achievements {
+    /// Number of achievements the user has unlocked.
+    pub unlocked: u32,
+    /// Total number of achievements in the game.
+    pub total: u32,
+}
+
+/// Installed DLC of a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Dlc {
+    /// ID of the DLC in the game's source, e.g. a Steam app ID.
+    pub id: String,
+    /// Name of the DLC, if known.
+    pub name: Option<String>,
+}
+
+/// Installed depot of a Steam game, as listed in its app manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Depot {
+    /// Depot ID.
+    pub depot_id: u32,
+    /// ID of the installed version of the depot's files.
+    pub manifest_id: u64,
+    /// Size of the depot's files, in bytes.
+    pub size: u64,
+    /// App ID of the DLC the depot belongs to, if it isn't part of the base game.
+    pub dlc_app_id: Option<u32>,
+}
+
+/// Compatibility tool used to run a game, e.g. Proton for a Windows game on Steam
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CompatTool {
+    /// Internal name of the tool, e.g. `proton_experimental` or `GE-Proton9-20`.
+    pub name: String,
+    /// Path to the tool's install directory, if it is installed.
+    pub path_tool: Option<PathBuf>,
+    /// Path to the game's prefix directory for the tool, if it exists.
+    pub path_prefix: Option<PathBuf>,
+}
+
+/// Install state of a game, for sources which report whether it is ready to launch
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum InstallState {
+    /// Fully installed and ready to launch
+    Installed,
+    /// Installed, but an update must be downloaded before it can be launched
+    UpdateRequired,
+    /// Being downloaded, either for the first install or an update
+    Downloading {
+        /// Bytes downloaded so far
+        bytes_downloaded: u64,
+        /// Total bytes to download
+        bytes_to_download: u64,
+        /// Whether the download is paused
+        is_paused: bool,
+    },
+    /// Being uninstalled
+    Uninstalling,
+    /// Some of the game's files are missing or corrupt, so they need to be validated
+    FilesMissing,
+}
+
+impl InstallState {
+    /// Returns `true` if the game is ready to launch.
+    #[must_use]
+    pub fn is_launchable(&self) -> bool {
+        matches!(self, InstallState::Installed)
+    }
+
+    /// Returns the download progress from `0.0` to `1.0`, if the game is being downloaded.
+    #[must_use]
+    pub fn get_download_progress(&self) -> Option<f64> {
+        match self {
+            InstallState::Downloading {
+                bytes_downloaded,
+                bytes_to_download,
+                ..
+            } if *bytes_to_download > 0 => {
+                Some((*bytes_downloaded as f64 / *bytes_to_download as f64).min(1.0))
+            }
+            InstallState::Downloading { .. } => Some(0.0),
+            _ => None,
+        }
+    }
+}
+
+/// How a launcher (or a game) was installed, which determines where its data is found and how
+/// games are launched through it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum InstallVariant {
+    /// Installed natively, e.g. through the system package manager
+    Native,
+    /// Installed as a flatpak app, with data under `~/.var/app/`
+    Flatpak,
+    /// Installed as a snap, with data under `~/snap/`
+    Snap,
+    /// Run from the AppImage at the given path
+    AppImage(PathBuf),
+    /// Installed at a custom location, with the given path to the executable
+    Custom(PathBuf),
+}
+
+impl InstallVariant {
+    /// Returns [`InstallVariant::AppImage`] if the given executable is an AppImage, otherwise
+    /// [`InstallVariant::Custom`].
+    #[must_use]
+    pub fn from_executable(path: PathBuf) -> Self {
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("appimage"))
+        {
+            Self::AppImage(path)
+        } else {
+            Self::Custom(path)
+        }
+    }
 }
 
 /// Data structure representing a supported games source
@@ -70,6 +272,12 @@ pub enum SupportedLaunchers {
     MinecraftAT,
     /// Itch.io games
     Itch,
+    /// Games installed natively, detected from `.desktop` files
+    DesktopEntries,
+    /// Games installed as flatpak apps, e.g. from Flathub
+    Flatpak,
+    /// Games installed as snaps, e.g. from the Snap Store
+    Snap,
 }
 
 /// Custom Result type for Games
@@ -93,6 +301,9 @@ impl Debug for SupportedLaunchers {
                 SupportedLaunchers::MinecraftPrism => "Prism Launcher",
                 SupportedLaunchers::MinecraftAT => "ATLauncher",
                 SupportedLaunchers::Itch => "Itch",
+                SupportedLaunchers::DesktopEntries => "Desktop entries",
+                SupportedLaunchers::Flatpak => "Flatpak",
+                SupportedLaunchers::Snap => "Snap",
             }
         )
     }
@@ -110,11 +321,65 @@ impl Display for SupportedLaunchers {
 pub trait Launcher: Send + Debug {
     /// Returns the [`SupportedLaunchers`] variant of this launcher.
     fn get_launcher_type(&self) -> SupportedLaunchers;
+    /// Returns the [`InstallVariant`] of this launcher.
+    fn get_install_variant(&self) -> InstallVariant;
     /// Returns `true` if this source is detected on the user's system.
     fn is_detected(&self) -> bool;
     /// Get all games detected from this source.
     fn get_detected_games(&self) -> GamesResult;
 }
+
+/// Options for configuring how games are detected, used with
+/// [`get_detector_with_options`](crate::get_detector_with_options).
+#[derive(Debug, Clone)]
+pub struct DetectorOptions {
+    /// When running inside a flatpak sandbox, wrap all launch commands in `flatpak-spawn --host`
+    /// so that they are run on the host. This requires the `--talk-name=org.freedesktop.Flatpak`
+    /// permission. Enabled by default.
+    pub spawn_on_host: bool,
+    /// Steam accounts to detect per-user data from, such as non-Steam games and playtime. All
+    /// accounts by default.
+    pub steam_accounts: SteamAccountFilter,
+    /// Include games which the user has hidden in the launcher, e.g. in a Steam library. Enabled
+    /// by default.
+    pub include_hidden: bool,
+    /// Extra directories to search for Steam in, e.g. if it is installed in a custom prefix.
+    /// These are searched before the default locations, and games are detected from every
+    /// distinct Steam directory found. Empty by default.
+    pub steam_dirs: Vec<PathBuf>,
+    /// Executables to launch games with for launchers which aren't installed through a package
+    /// manager, e.g. `(SupportedLaunchers::Lutris, "~/Applications/Lutris.AppImage")`. The
+    /// launcher's data is read from the same locations as a native install, so the executable is
+    /// used instead of the native install. An executable given for any of the sources of the same
+    /// launcher (e.g. any of the Heroic sources, or Steam and Steam shortcuts) is used for all of
+    /// them. Empty by default.
+    pub launcher_executables: Vec<(SupportedLaunchers, PathBuf)>,
+}
+
+impl Default for DetectorOptions {
+    fn default() -> Self {
+        Self {
+            spawn_on_host: true,
+            steam_accounts: SteamAccountFilter::default(),
+            include_hidden: true,
+            steam_dirs: Vec::new(),
+            launcher_executables: Vec::new(),
+        }
+    }
+}
+
+/// Steam accounts to detect per-user data from, used with [`DetectorOptions`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SteamAccountFilter {
+    /// All accounts with data on this system
+    #[default]
+    All,
+    /// Only the account which logged in most recently, i.e. the active one
+    MostRecent,
+    /// Only the account with the given 64-bit SteamID
+    SteamId(u64),
+}
+
 /// Container for [`Launcher`].
 pub type Launchers = Vec<Arc<dyn Launcher>>;
 /// Container for games divided by their source [`SupportedLaunchers`].
@@ -129,9 +394,11 @@ pub trait GamesDetector {
     fn get_all_detected_games(&self) -> Vec<Game>;
     /// Returns all detected games from all detected launchers, which also have detected box art.
     fn get_all_detected_games_with_box_art(&self) -> Vec<Game>;
-    /// Returns all detected games divided by their source launchers.
+    /// Returns all detected games divided by their source launchers. Games from all install
+    /// variants of a launcher are grouped together.
     fn get_all_detected_games_per_launcher(&self) -> GamesPerLauncher;
-    /// Returns all detected games from a specific launcher, identified by [`SupportedLaunchers`].
+    /// Returns all detected games from a specific launcher, identified by [`SupportedLaunchers`],
+    /// across all of its detected install variants.
     fn get_all_detected_games_from_specific_launcher(
         &self,
         launcher_type: SupportedLaunchers,
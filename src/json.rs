@@ -0,0 +1,272 @@
+//! Parser for JSON files, such as Heroic's library files and Steam's cloud storage for
+//! collections, giving a tree of [`Value`]s which can then be read into typed structs.
+
+use std::{fs::read_to_string, path::Path, str::CharIndices};
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, multispace0},
+    combinator::{eof, value},
+    error::{Error, ErrorKind},
+    multi::separated_list0,
+    number::complete::recognize_float,
+    sequence::{delimited, preceded, separated_pair, terminated},
+};
+
+use crate::error::GamesParsingError;
+
+/// A JSON value. Numbers are kept as they are written, so that large integers don't lose
+/// precision.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    /// Key/value pairs, in the order they were defined.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value of the given key, or [`None`] if this is not an object.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as a [`u64`], or [`None`] if it is negative or not a whole number.
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(o) => Some(o),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value of the given key.
+    #[must_use]
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    /// Returns the boolean value of the given key.
+    #[must_use]
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(Value::as_bool)
+    }
+
+    /// Returns the value of the given key as a [`u64`].
+    #[must_use]
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get(key).and_then(Value::as_u64)
+    }
+
+    /// Returns the array value of the given key.
+    #[must_use]
+    pub fn get_array(&self, key: &str) -> Option<&[Value]> {
+        self.get(key).and_then(Value::as_array)
+    }
+}
+
+// PARSERS ----------------------------------------------------------------------------------------
+/// Parse a quoted string, handling all escape sequences including UTF-16 surrogate pairs
+fn parse_string(input: &str) -> IResult<&str, String> {
+    let (rest, _) = char('"').parse(input)?;
+    let mut parsed = String::new();
+    let mut chars = rest.char_indices();
+    let error = || nom::Err::Error(Error::new(input, ErrorKind::Char));
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((&rest[i + 1..], parsed)),
+            '\\' => match chars.next().ok_or_else(error)?.1 {
+                'n' => parsed.push('\n'),
+                't' => parsed.push('\t'),
+                'r' => parsed.push('\r'),
+                'b' => parsed.push('\u{8}'),
+                'f' => parsed.push('\u{c}'),
+                'u' => {
+                    let parse_hex = |chars: &mut CharIndices| -> Option<u32> {
+                        let hex = (0..4)
+                            .map(|_| chars.next().map(|(_, c)| c))
+                            .collect::<Option<String>>()?;
+                        u32::from_str_radix(&hex, 16).ok()
+                    };
+
+                    let to_char = |code_point| {
+                        char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER)
+                    };
+
+                    let high = parse_hex(&mut chars).ok_or_else(error)?;
+                    if (0xD800..0xDC00).contains(&high) {
+                        // Characters outside the BMP are escaped as a pair of surrogates
+                        let (Some((_, '\\')), Some((_, 'u'))) = (chars.next(), chars.next()) else {
+                            return Err(error());
+                        };
+                        let low = parse_hex(&mut chars).ok_or_else(error)?;
+                        if (0xDC00..0xE000).contains(&low) {
+                            parsed
+                                .push(to_char(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)));
+                        } else {
+                            // A high surrogate without a low surrogate is invalid on its own, but
+                            // the character after it is still kept
+                            parsed.push(char::REPLACEMENT_CHARACTER);
+                            parsed.push(to_char(low));
+                        }
+                    } else {
+                        parsed.push(to_char(high));
+                    }
+                }
+                c => parsed.push(c),
+            },
+            c => parsed.push(c),
+        }
+    }
+
+    Err(error())
+}
+
+fn parse_array(input: &str) -> IResult<&str, Vec<Value>> {
+    delimited(
+        char('['),
+        separated_list0(char(','), parse_value),
+        preceded(multispace0, char(']')),
+    )
+    .parse(input)
+}
+
+fn parse_object(input: &str) -> IResult<&str, Vec<(String, Value)>> {
+    delimited(
+        char('{'),
+        separated_list0(
+            char(','),
+            separated_pair(
+                preceded(multispace0, parse_string),
+                preceded(multispace0, char(':')),
+                parse_value,
+            ),
+        ),
+        preceded(multispace0, char('}')),
+    )
+    .parse(input)
+}
+
+fn parse_value(input: &str) -> IResult<&str, Value> {
+    delimited(
+        multispace0,
+        alt((
+            parse_object.map(Value::Object),
+            parse_array.map(Value::Array),
+            parse_string.map(Value::String),
+            recognize_float.map(|n: &str| Value::Number(n.to_owned())),
+            value(Value::Bool(true), tag("true")),
+            value(Value::Bool(false), tag("false")),
+            value(Value::Null, tag("null")),
+        )),
+        multispace0,
+    )
+    .parse(input)
+}
+
+/// Parse the contents of a JSON file
+pub fn parse(input: &str) -> Result<Value, GamesParsingError> {
+    let (_, parsed) = terminated(parse_value, eof).parse(input)?;
+    Ok(parsed)
+}
+
+/// Read and parse a JSON file
+pub fn parse_file(path: &Path) -> Result<Value, GamesParsingError> {
+    parse(&read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), GamesParsingError> {
+        let parsed = parse(
+            r#"
+            {
+                "title": "Game \"Name\" \u00e9\ud83d\ude00",
+                "unpaired": "\ud83d\u0041",
+                "app_id": 3000000001,
+                "is_installed": true,
+                "nested": { "list": [1, -2.5e3, null, "x"] },
+                "empty": {}
+            }
+            "#,
+        )?;
+
+        assert_eq!(parsed.get_str("title"), Some("Game \"Name\" é😀"));
+        // A high surrogate without a low surrogate doesn't swallow the next character
+        assert_eq!(parsed.get_str("unpaired"), Some("\u{FFFD}A"));
+        assert_eq!(parsed.get_u64("app_id"), Some(3000000001));
+        assert_eq!(parsed.get_bool("is_installed"), Some(true));
+        assert_eq!(
+            parsed.get("nested").and_then(|n| n.get_array("list")),
+            Some(
+                [
+                    Value::Number(String::from("1")),
+                    Value::Number(String::from("-2.5e3")),
+                    Value::Null,
+                    Value::String(String::from("x")),
+                ]
+                .as_slice()
+            )
+        );
+        assert_eq!(parsed.get("empty"), Some(&Value::Object(vec![])));
+        assert!(parsed.get("missing").is_none());
+
+        Ok(())
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("{\"key\": }"; "missing value")]
+    #[test_case("[1, 2"; "unclosed array")]
+    #[test_case("\"unclosed"; "unclosed string")]
+    #[test_case("{} {}"; "trailing content")]
+    fn test_parse_invalid(input: &str) {
+        assert!(parse(input).is_err());
+    }
+}
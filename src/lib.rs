@@ -49,6 +49,23 @@
 //! let all_games_from_steam = detector.get_all_detected_games_from_specific_launcher(SupportedLaunchers::Steam);
 //! ```
 //!
+//! When running inside a flatpak sandbox, games are detected using the host's directories, and
+//! launch commands are wrapped in `flatpak-spawn --host` so that they run on the host (this requires
+//! the `--talk-name=org.freedesktop.Flatpak` permission). The wrapping can be disabled with:
+//!
+//! ```rust
+//! use lib_game_detector::{data::DetectorOptions, get_detector_with_options};
+//!
+//! let detector = get_detector_with_options(DetectorOptions {
+//!     spawn_on_host: false,
+//!     ..Default::default()
+//! });
+//! ```
+//!
+//! Steam-specific data which isn't part of a game, such as Steam's library folders (including those
+//! which don't exist, e.g. on drives which aren't mounted), the accounts which have logged in and
+//! each game's Workshop items, is available from the [`steam`] module.
+//!
 //! # Examples
 //!
 //! - Checkout [rofi-games](https://github.com/Rolv-Apneseth/rofi-games) or [rgd](https://github.com/Rolv-Apneseth/rgd)
@@ -57,17 +74,63 @@
 //!
 //! # Currently supported game sources
 //!
+//! Launchers installed natively or as a flatpak are supported, and Steam, Heroic, Lutris, Bottles and
+//! Itch are also supported when installed as a snap.
+//!
+//! If a launcher is installed in more than one way (e.g. both natively and as a flatpak), games are
+//! detected from each install, and every game records the `InstallVariant` which launches it.
+//! Launchers run from an AppImage or a custom location can be given with
+//! `DetectorOptions::launcher_executables`, and their games are then launched with that executable.
+//!
 //! - Steam
-//!   - Non-Steam games added as shortcuts are also supported. Just make sure to launch newly added
-//!     shortcuts through Steam at at least once for them to be detected correctly (some files need
-//!     to be generated).
+//!   - Steam is searched for in `$STEAM_DIR`, `$STEAM_COMPAT_CLIENT_INSTALL_PATH`, `~/.steam`
+//!     (including Debian's `debian-installation`), `$XDG_DATA_HOME/Steam` and the flatpak and snap
+//!     data directories, as well as any directories given with `DetectorOptions::steam_dirs`.
+//!     Games are detected from every distinct Steam directory found.
+//!   - Apps are classified (e.g. game, tool, DLC) and given metadata such as their developer and
+//!     genres using Steam's `appinfo.vdf` cache. If it is unavailable, only apps with box art are
+//!     listed.
+//!   - Each game's install state (e.g. downloading, update required) is given, so that games which
+//!     can't currently be launched can be identified.
+//!   - Launch options set by the user (e.g. `gamemoderun %command%`) are given, and a command
+//!     launching a game with extra arguments through Steam's `-applaunch` option can be built from
+//!     the `steam` module.
+//!   - Each game's installed DLC (with their names, if `appinfo.vdf` is available) and depots are
+//!     given. DLC installed with their own app manifest are not listed as separate games.
+//!   - For games run through Proton (or another compatibility tool), the tool's name, install path
+//!     and the game's prefix are given. This includes non-Steam games.
+//!   - Non-Steam games added as shortcuts are also supported, and are detected as soon as they
+//!     are added (they don't need to be launched through Steam first). Their executable, starting
+//!     directory, launch options, tags, hidden status and last played time are given too.
+//!   - Games from any other launcher can be added to Steam as shortcuts (including their box art
+//!     and icon) using the `steam` module's `ShortcutsWriter`, e.g. to play them in Big Picture mode.
+//!   - Every Steam account which has logged in is supported, and which accounts are used can be
+//!     chosen with `DetectorOptions::steam_accounts`. Each game's last played time, playtime, custom
+//!     box art, screenshots and achievement progress come from the account which played it most
+//!     recently. Screenshots are also given for non-Steam games.
+//!   - The collections each game is in, and whether it is a favourite or hidden, are given too.
+//!     Hidden games can be excluded with `DetectorOptions::include_hidden`.
 //! - Heroic Games Launcher (Legendary, Nile, GOG, and manually added games)
+//!   - Each game's settings are given, falling back to Heroic's global defaults: its Wine (or
+//!     Proton) version and prefix, environment variables, launch arguments, target executable and
+//!     whether GameMode and MangoHud are enabled.
+//!   - Each game's last played time and total playtime are given, for games launched through Heroic.
+//!   - GOG games' titles come from Heroic's library cache, along with their box art, cover, logo and
+//!     background images if Heroic has cached them.
 //! - Lutris
 //! - Bottles
 //!   - Only lists entries included in the Library
 //! - Modded Minecraft (Prism Launcher, ATLauncher)
 //!   - Titles are given as `Minecraft - {instance name}`
 //! - Itch ([itch.io](https://itch.io) app)
+//! - Native games with a `.desktop` file (e.g. installed by the system package manager, or AppImages
+//!   with desktop integration)
+//!   - Only entries in the `Game` category are listed, and entries which belong to any of the above
+//!     sources are skipped
+//! - Flatpak apps (e.g. from [Flathub](https://flathub.org)), for both user and system installations
+//!   - Only apps in the `Game` category are listed, and launchers supported above are skipped
+//! - Snaps (e.g. from the [Snap Store](https://snapcraft.io/store))
+//!   - Only apps in the `Game` category are listed, and launchers supported above are skipped
 
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
@@ -78,16 +141,19 @@ use cfg_if::cfg_if;
 
 pub mod data;
 pub mod error;
+mod json;
 mod macros;
 mod parsers;
 mod utils;
+pub mod vdf;
 
-use data::GamesDetector;
+use data::{DetectorOptions, GamesDetector};
 
 cfg_if! {
     if #[cfg(all(target_os = "linux"))] {
         mod linux;
         use linux::GamesDetectorLinux;
+        pub use linux::launchers::steam;
         type TGamesDetector = GamesDetectorLinux;
     } else {
         compiler_error!("This platform is currently not supported by lib_game_detector");
@@ -100,6 +166,12 @@ pub fn get_detector() -> Box<dyn GamesDetector> {
     Box::new(TGamesDetector::default())
 }
 
+/// Get a [`GamesDetector`] configured with the given [`DetectorOptions`]
+#[must_use]
+pub fn get_detector_with_options(options: DetectorOptions) -> Box<dyn GamesDetector> {
+    Box::new(TGamesDetector::new(options))
+}
+
 // TODO: use [macro_files](https://github.com/MathieuTricoire/macro_files) to reduce size of repo
 // and more importantly to have clear definitions of expected file structures for each test. All
 // the example game files will still need to be included so as to not clutter the code though.
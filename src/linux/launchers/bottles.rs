@@ -1,6 +1,7 @@
 // PATHS:
 // - ~/.local/share/bottles/
 // - Flatpak: ~/.var/app/com.usebottles.bottles/data/bottles/
+// - Snap: ~/snap/bottles/common/.local/share/bottles/
 use std::{
     fs::{read_dir, read_to_string},
     io,
@@ -17,13 +18,14 @@ use nom::{
 use tracing::{error, trace, warn};
 
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
-    macros::logs::{debug_fallback_flatpak, debug_path, warn_no_games},
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    macros::logs::{debug_path, warn_no_games},
     parsers::{
         parse_not_alphanumeric, parse_till_end_of_line, parse_until_key_yml, parse_value_yml,
     },
     utils::{
-        clean_game_title, get_launch_command, get_launch_command_flatpak, some_if_dir, some_if_file,
+        clean_game_title, get_launch_command, get_launch_command_flatpak, get_launch_command_snap,
+        some_if_dir, some_if_file,
     },
 };
 
@@ -175,20 +177,20 @@ fn parse_game_from_library<'a>(file_content: &'a str) -> IResult<&'a str, Parsab
 pub struct Bottles {
     path_bottles_dir: PathBuf,
     path_bottles_library: PathBuf,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
 }
 
 impl Bottles {
-    pub fn new(path_home: &Path, path_data: &Path) -> Self {
-        let mut path_bottles_data = path_data.join("bottles");
-        let mut is_using_flatpak = false;
-
-        if !path_bottles_data.is_dir() {
-            debug_fallback_flatpak!();
-
-            is_using_flatpak = true;
-            path_bottles_data = path_home.join(".var/app/com.usebottles.bottles/data/bottles");
-        }
+    pub fn new(path_home: &Path, path_data: &Path, install_variant: InstallVariant) -> Self {
+        let path_bottles_data = match install_variant {
+            InstallVariant::Flatpak => {
+                path_home.join(".var/app/com.usebottles.bottles/data/bottles")
+            }
+            InstallVariant::Snap => path_home.join("snap/bottles/common/.local/share/bottles"),
+            InstallVariant::Native | InstallVariant::AppImage(_) | InstallVariant::Custom(_) => {
+                path_data.join("bottles")
+            }
+        };
 
         let path_bottles_dir = path_bottles_data.join("bottles");
         let path_bottles_library = path_bottles_data.join("library.yml");
@@ -200,7 +202,7 @@ impl Bottles {
         Bottles {
             path_bottles_dir,
             path_bottles_library,
-            is_using_flatpak,
+            install_variant,
         }
     }
 
@@ -299,6 +301,10 @@ impl Launcher for Bottles {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let parsed_data = self.parse_game_data()?;
@@ -320,15 +326,22 @@ impl Launcher for Bottles {
                  }| {
                     let launch_command = {
                         let base_args = ["run", "-p", &title, "-b", &bottle_name];
-                        if self.is_using_flatpak {
-                            get_launch_command_flatpak(
+                        match &self.install_variant {
+                            InstallVariant::Native => {
+                                get_launch_command("bottles-cli", base_args, [])
+                            }
+                            InstallVariant::Flatpak => get_launch_command_flatpak(
                                 "com.usebottles.bottles",
                                 ["--command=bottles-cli"],
                                 base_args,
                                 [],
-                            )
-                        } else {
-                            get_launch_command("bottles-cli", base_args, [])
+                            ),
+                            InstallVariant::Snap => {
+                                get_launch_command_snap("bottles.bottles-cli", base_args, [])
+                            }
+                            InstallVariant::AppImage(path) | InstallVariant::Custom(path) => {
+                                get_launch_command(path, base_args, [])
+                            }
                         }
                     };
                     trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
@@ -353,6 +366,8 @@ impl Launcher for Bottles {
                         path_box_art,
                         path_game_dir,
                         source: LAUNCHER.clone(),
+                        install_variant: self.install_variant.clone(),
+                        metadata: GameMetadata::default(),
                     }
                 },
             )
@@ -367,23 +382,27 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".local/share"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, "", ".local/share"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/data/path"; "snap")]
     fn test_bottles_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_data: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = Bottles::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_data),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let games = launcher.get_detected_games()?;
         assert_eq!(games.len(), 4);
+        assert!(games.iter().all(|g| g.install_variant == install_variant));
 
         assert_eq!(games[0].title, "Warcraft III");
         assert_eq!(games[1].title, "GOG Galaxy");
@@ -407,4 +426,35 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_bottles_flatpak_launch_command() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = Bottles::new(
+            &path_file_system_mock,
+            &path_file_system_mock.join("invalid/data/path"),
+            InstallVariant::Flatpak,
+        );
+
+        let games = launcher.get_detected_games()?;
+        let launch_command = &games[0].launch_command;
+
+        // Options for `flatpak run` must come before the app ID
+        assert_eq!(launch_command.get_program(), "flatpak");
+        assert_eq!(
+            launch_command.get_args().collect::<Vec<_>>(),
+            [
+                "run",
+                "--command=bottles-cli",
+                "com.usebottles.bottles",
+                "run",
+                "-p",
+                "Warcraft III",
+                "-b",
+                "Warcraft Bottle",
+            ]
+        );
+
+        Ok(())
+    }
 }
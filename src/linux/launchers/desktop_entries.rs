@@ -0,0 +1,447 @@
+// PATHS:
+// - $XDG_DATA_HOME/applications/
+// - $XDG_DATA_DIRS/applications/ (e.g. /usr/share/applications/)
+use std::{
+    collections::HashSet,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tracing::{debug, trace, warn};
+use walkdir::WalkDir;
+
+use crate::{
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    macros::logs::warn_no_games,
+    utils::{clean_game_title, some_if_dir, some_if_file},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::DesktopEntries;
+
+/// Executables of launchers which are already handled by other sources, so their own desktop
+/// entries (and any entries which launch a game through them) should be skipped
+const SUPPORTED_LAUNCHER_EXECUTABLES: [&str; 9] = [
+    "steam",
+    "heroic",
+    "lutris",
+    "bottles",
+    "bottles-cli",
+    "prismlauncher",
+    "atlauncher",
+    "itch",
+    "flatpak",
+];
+
+/// URL schemes used by launchers which are already handled by other sources
+const SUPPORTED_LAUNCHER_URLS: [&str; 3] = ["steam://", "heroic://", "lutris:"];
+
+/// Icon sizes to check within an icon theme, in order of preference
+const ICON_SIZES: [&str; 9] = [
+    "512x512", "256x256", "192x192", "128x128", "96x96", "64x64", "48x48", "32x32", "scalable",
+];
+
+/// Relevant data parsed from the `[Desktop Entry]` group of a `.desktop` file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DesktopEntry {
+    pub name: String,
+    pub exec: Option<String>,
+    pub icon: Option<String>,
+    pub path: Option<String>,
+    pub categories: Vec<String>,
+    pub entry_type: Option<String>,
+    pub no_display: bool,
+    pub hidden: bool,
+}
+
+impl DesktopEntry {
+    /// Returns `true` if this entry should be listed as a game
+    pub fn is_visible_game(&self) -> bool {
+        self.entry_type
+            .as_deref()
+            .is_none_or(|t| t == "Application")
+            && !self.no_display
+            && !self.hidden
+            && self.categories.iter().any(|c| c == "Game")
+    }
+
+    /// Returns the arguments of the `Exec` key, with field codes removed
+    pub fn exec_args(&self) -> Vec<String> {
+        self.exec.as_deref().map(split_exec).unwrap_or_default()
+    }
+
+    /// Returns `true` if this entry belongs to, or launches a game through, a launcher which is
+    /// already supported by another source
+    pub fn is_supported_launcher(&self) -> bool {
+        let args = self.exec_args();
+
+        if args
+            .iter()
+            .any(|a| SUPPORTED_LAUNCHER_URLS.iter().any(|u| a.starts_with(u)))
+        {
+            return true;
+        }
+
+        // Entries exported by snaps (to `/var/lib/snapd/desktop`) are handled by the snap source
+        if args.iter().any(|a| a.starts_with("/snap/bin/")) {
+            return true;
+        }
+
+        args.first()
+            .and_then(|a| Path::new(a).file_name())
+            .and_then(|f| f.to_str())
+            .is_some_and(|f| SUPPORTED_LAUNCHER_EXECUTABLES.contains(&f))
+    }
+
+    /// Build a launch command from the `Exec` key, using `Path` as the working directory
+    pub fn get_launch_command(&self) -> Option<Command> {
+        let args = self.exec_args();
+        let (program, args) = args.split_first()?;
+
+        let mut command = Command::new(program);
+        command.args(args);
+        if let Some(path) = self.path.as_ref().filter(|p| !p.is_empty()) {
+            command.current_dir(path);
+        }
+
+        Some(command)
+    }
+
+    /// Returns [`InstallVariant::AppImage`] if this entry runs an AppImage (e.g. one integrated
+    /// into the desktop by AppImageLauncher), otherwise [`InstallVariant::Native`]
+    pub fn get_install_variant(&self) -> InstallVariant {
+        self.exec_args()
+            .first()
+            .map(PathBuf::from)
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("appimage"))
+            })
+            .map_or(InstallVariant::Native, InstallVariant::AppImage)
+    }
+}
+
+// UTILS --------------------------------------------------------------------------------
+/// Unescape a desktop entry string value (see the `string` type in the Desktop Entry spec)
+fn unescape_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+/// Parse the `[Desktop Entry]` group of a `.desktop` file. Localised keys are ignored.
+#[tracing::instrument(level = "trace", skip(file_content))]
+pub fn parse_desktop_entry(file_content: &str) -> Option<DesktopEntry> {
+    let mut entry = DesktopEntry::default();
+    let mut is_in_main_group = false;
+    let mut has_main_group = false;
+
+    for line in file_content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            is_in_main_group = line == "[Desktop Entry]";
+            has_main_group |= is_in_main_group;
+            continue;
+        }
+
+        if !is_in_main_group {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = unescape_value(value.trim());
+
+        match key.trim() {
+            "Name" => entry.name = value,
+            "Exec" => entry.exec = Some(value),
+            "Icon" => entry.icon = Some(value),
+            "Path" => entry.path = Some(value),
+            "Type" => entry.entry_type = Some(value),
+            "NoDisplay" => entry.no_display = value == "true",
+            "Hidden" => entry.hidden = value == "true",
+            "Categories" => {
+                entry.categories = value
+                    .split(';')
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            }
+            _ => {}
+        }
+    }
+
+    (has_main_group && !entry.name.is_empty()).then_some(entry)
+}
+
+/// Split the value of an `Exec` key into its arguments, handling quoting and removing any field
+/// codes (e.g. `%U`), as described by the Desktop Entry spec
+pub fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut is_quoted = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => is_quoted = !is_quoted,
+            '\\' if is_quoted => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            // Field codes are dropped, other than the escaped `%%`
+            '%' => {
+                if chars.next() == Some('%') {
+                    current.push('%');
+                }
+            }
+            c if c.is_whitespace() && !is_quoted => args.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    args.push(current);
+
+    // Field codes and repeated whitespace leave behind empty arguments
+    args.into_iter().filter(|a| !a.is_empty()).collect()
+}
+
+/// Resolve the value of an `Icon` key to a file path, by checking the `hicolor` icon theme and
+/// the `pixmaps` directory within each of the given data directories
+pub fn find_icon(icon: &str, paths_data_dirs: &[PathBuf]) -> Option<PathBuf> {
+    if icon.is_empty() {
+        return None;
+    }
+
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return some_if_file(path.to_path_buf());
+    }
+
+    paths_data_dirs.iter().find_map(|path_data_dir| {
+        let path_theme = path_data_dir.join("icons/hicolor");
+
+        ICON_SIZES
+            .iter()
+            .find_map(|size| find_icon_file(&path_theme.join(size).join("apps"), icon))
+            .or_else(|| find_icon_file(&path_data_dir.join("pixmaps"), icon))
+    })
+}
+
+fn find_icon_file(path_dir: &Path, icon: &str) -> Option<PathBuf> {
+    ["png", "svg", "xpm"]
+        .iter()
+        .find_map(|ext| some_if_file(path_dir.join(format!("{icon}.{ext}"))))
+}
+
+/// Find all `.desktop` files in the given `applications` directories, ordered by precedence.
+/// Entries in earlier directories shadow entries with the same desktop file ID in later ones.
+pub fn find_desktop_files(paths_applications: &[PathBuf]) -> Vec<PathBuf> {
+    let mut seen_ids = HashSet::new();
+
+    paths_applications
+        .iter()
+        .flat_map(|path_applications| {
+            WalkDir::new(path_applications)
+                .min_depth(1)
+                .sort_by_file_name()
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "desktop"))
+                .filter_map(move |e| {
+                    let id = e
+                        .path()
+                        .strip_prefix(path_applications)
+                        .ok()?
+                        .to_string_lossy()
+                        .replace('/', "-");
+                    Some((id, e.into_path()))
+                })
+        })
+        .filter_map(|(id, path)| seen_ids.insert(id).then_some(path))
+        .collect()
+}
+
+// DESKTOP ENTRIES LAUNCHER --------------------------------------------------------------
+#[derive(Debug)]
+pub struct DesktopEntries {
+    paths_applications: Vec<PathBuf>,
+    paths_data_dirs: Vec<PathBuf>,
+}
+
+impl DesktopEntries {
+    pub fn new(path_data: &Path, paths_data_dirs: &[PathBuf]) -> Self {
+        let paths_data_dirs: Vec<PathBuf> = std::iter::once(path_data.to_path_buf())
+            .chain(paths_data_dirs.iter().cloned())
+            .collect();
+        let paths_applications = paths_data_dirs
+            .iter()
+            .map(|p| p.join("applications"))
+            .collect();
+
+        debug!("{LAUNCHER} - applications directories: {paths_applications:?}");
+
+        Self {
+            paths_applications,
+            paths_data_dirs,
+        }
+    }
+
+    /// Parse a single game from the given `.desktop` file
+    #[tracing::instrument(level = "trace")]
+    fn get_game(&self, path_desktop_file: &Path) -> Option<Game> {
+        let file_content = read_to_string(path_desktop_file)
+            .map_err(|e| warn!("{LAUNCHER} - failed to read {path_desktop_file:?}: {e}"))
+            .ok()?;
+        let entry = parse_desktop_entry(&file_content)?;
+
+        if !entry.is_visible_game() {
+            trace!("{LAUNCHER} - Skipped entry which is not a visible game: {path_desktop_file:?}");
+            return None;
+        }
+
+        if entry.is_supported_launcher() {
+            trace!(
+                "{LAUNCHER} - Skipped entry belonging to a supported launcher: {path_desktop_file:?}"
+            );
+            return None;
+        }
+
+        let launch_command = entry.get_launch_command()?;
+        let install_variant = entry.get_install_variant();
+        let title = clean_game_title(&entry.name);
+        trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
+
+        let path_icon = entry
+            .icon
+            .as_deref()
+            .and_then(|i| find_icon(i, &self.paths_data_dirs));
+        let path_game_dir = entry
+            .path
+            .as_ref()
+            .filter(|p| !p.is_empty())
+            .and_then(|p| some_if_dir(PathBuf::from(p)));
+
+        trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
+        trace!("{LAUNCHER} - Icon for '{title}': {path_icon:?}");
+
+        Some(Game {
+            title,
+            launch_command,
+            path_box_art: None,
+            path_game_dir,
+            path_icon,
+            source: LAUNCHER.clone(),
+            install_variant,
+            metadata: GameMetadata::default(),
+        })
+    }
+}
+
+impl Launcher for DesktopEntries {
+    fn get_launcher_type(&self) -> SupportedLaunchers {
+        LAUNCHER
+    }
+
+    fn get_install_variant(&self) -> InstallVariant {
+        InstallVariant::Native
+    }
+
+    fn is_detected(&self) -> bool {
+        self.paths_applications.iter().any(|p| p.is_dir())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn get_detected_games(&self) -> GamesResult {
+        let games: Vec<Game> = find_desktop_files(&self.paths_applications)
+            .iter()
+            .filter_map(|p| self.get_game(p))
+            .collect();
+
+        if games.is_empty() {
+            warn_no_games!();
+        }
+
+        Ok(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
+
+    #[test_case("game", &["game"]; "single")]
+    #[test_case("game --fullscreen %U", &["game", "--fullscreen"]; "field code")]
+    #[test_case("\"/opt/My Game/game\" %f", &["/opt/My Game/game"]; "quoted")]
+    #[test_case("env VAR=1 game --level=\"a \\\"b\\\"\"", &["env", "VAR=1", "game", "--level=a \"b\""]; "escaped")]
+    #[test_case("game --percent=100%%", &["game", "--percent=100%"]; "escaped percent")]
+    fn test_split_exec(exec: &str, expected: &[&str]) {
+        assert_eq!(split_exec(exec), expected);
+    }
+
+    #[test]
+    fn test_desktop_entries_launcher() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = DesktopEntries::new(
+            &path_file_system_mock.join(".local/share"),
+            &[path_file_system_mock.join("usr/share")],
+        );
+
+        assert!(launcher.is_detected());
+
+        let mut games = launcher.get_detected_games()?;
+        games.sort_by_key(|g| g.title.clone());
+
+        assert_eq!(games.len(), 3);
+
+        assert_eq!(games[0].title, "Celeste");
+        assert_eq!(games[1].title, "OpenTTD (local)");
+        assert_eq!(games[2].title, "Xonotic");
+
+        assert_eq!(games[1].launch_command.get_program(), "openttd");
+        assert_eq!(
+            games[2].launch_command.get_args().collect::<Vec<_>>(),
+            ["-basedir", "/usr/share/xonotic"]
+        );
+
+        assert!(games[0].path_icon.is_none());
+        assert!(games[1].path_icon.is_some());
+        assert!(games[2].path_icon.is_some());
+
+        assert_eq!(
+            games[0].install_variant,
+            InstallVariant::AppImage(PathBuf::from("/not/a/dir/Applications/Celeste.AppImage"))
+        );
+        assert_eq!(games[1].install_variant, InstallVariant::Native);
+        assert_eq!(games[2].install_variant, InstallVariant::Native);
+
+        assert!(games.iter().all(|g| g.path_box_art.is_none()));
+
+        Ok(())
+    }
+}
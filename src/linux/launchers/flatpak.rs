@@ -0,0 +1,236 @@
+// PATHS:
+// - ~/.local/share/flatpak/app/
+// - /var/lib/flatpak/app/
+use std::{
+    collections::HashSet,
+    fs::{read_dir, read_to_string},
+    path::{Path, PathBuf},
+};
+
+use tracing::{debug, trace, warn};
+
+use super::desktop_entries::{find_desktop_files, find_icon, parse_desktop_entry};
+use crate::{
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    macros::logs::{debug_path, warn_no_games},
+    utils::{clean_game_title, get_launch_command_flatpak, some_if_dir},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Flatpak;
+
+/// IDs of flatpak apps which are launchers already handled by other sources
+const SUPPORTED_LAUNCHER_IDS: [&str; 7] = [
+    "com.valvesoftware.Steam",
+    "com.heroicgameslauncher.hgl",
+    "net.lutris.Lutris",
+    "com.usebottles.bottles",
+    "org.prismlauncher.PrismLauncher",
+    "com.atlauncher.ATLauncher",
+    "io.itch.itch",
+];
+
+/// An installed flatpak app, found in one of the flatpak installations
+#[derive(Debug)]
+struct InstalledApp {
+    app_id: String,
+    path_active: PathBuf,
+}
+
+impl InstalledApp {
+    /// Directory containing the files exported by the app (desktop entries, icons, etc.)
+    fn path_export(&self) -> PathBuf {
+        self.path_active.join("export/share")
+    }
+}
+
+// FLATPAK LAUNCHER ---------------------------------------------------------------------
+#[derive(Debug)]
+pub struct Flatpak {
+    paths_installations: Vec<PathBuf>,
+}
+
+impl Flatpak {
+    pub fn new(path_data: &Path, path_system_installation: &Path) -> Self {
+        let path_user_apps = path_data.join("flatpak/app");
+        let path_system_apps = path_system_installation.join("app");
+
+        debug_path!("user installation apps directory", path_user_apps);
+        debug_path!("system installation apps directory", path_system_apps);
+
+        Self {
+            paths_installations: vec![path_user_apps, path_system_apps],
+        }
+    }
+
+    /// Find all installed apps, excluding launchers handled by other sources. Apps installed for
+    /// the user take precedence over the same app installed system-wide, as `flatpak run` does.
+    #[tracing::instrument(level = "trace")]
+    fn get_installed_apps(&self) -> Vec<InstalledApp> {
+        let mut seen_ids = HashSet::new();
+
+        self.paths_installations
+            .iter()
+            .filter_map(|path_apps| {
+                read_dir(path_apps)
+                    .inspect_err(|e| debug!("{LAUNCHER} - could not read {path_apps:?}: {e}"))
+                    .ok()
+            })
+            .flat_map(|dir| dir.flatten())
+            .filter_map(|dir_entry| {
+                let app_id = dir_entry.file_name().to_str()?.to_owned();
+                let path_active = some_if_dir(dir_entry.path().join("current/active"))?;
+
+                Some(InstalledApp {
+                    app_id,
+                    path_active,
+                })
+            })
+            .filter(|app| !SUPPORTED_LAUNCHER_IDS.contains(&app.app_id.as_str()))
+            .filter(|app| seen_ids.insert(app.app_id.clone()))
+            .collect()
+    }
+
+    /// Get all games from the desktop entries exported by the given app
+    #[tracing::instrument(level = "trace")]
+    fn get_games(&self, app: &InstalledApp) -> Vec<Game> {
+        let path_export = app.path_export();
+        let paths_data_dirs = [path_export.clone()];
+
+        find_desktop_files(&[path_export.join("applications")])
+            .into_iter()
+            .filter_map(|path_desktop_file| {
+                let file_content = read_to_string(&path_desktop_file)
+                    .map_err(|e| warn!("{LAUNCHER} - failed to read {path_desktop_file:?}: {e}"))
+                    .ok()?;
+                let entry = parse_desktop_entry(&file_content)?;
+
+                if !entry.is_visible_game() {
+                    trace!(
+                        "{LAUNCHER} - Skipped entry which is not a visible game: {path_desktop_file:?}"
+                    );
+                    return None;
+                }
+
+                // Exported entries look like `flatpak run [flatpak args] app.id [args]`, so keep
+                // any arguments given, e.g. `--command=...`
+                let exec_args = entry.exec_args();
+                let (flatpak_args, other_args) = exec_args
+                    .iter()
+                    .position(|a| *a == app.app_id)
+                    .filter(|&i| i >= 2 && exec_args[1] == "run")
+                    .map_or((&[][..], &[][..]), |i| {
+                        (&exec_args[2..i], &exec_args[i + 1..])
+                    });
+
+                let launch_command = get_launch_command_flatpak(
+                    &app.app_id,
+                    flatpak_args.iter().map(String::as_str),
+                    other_args.iter().map(String::as_str),
+                    [],
+                );
+
+                let title = clean_game_title(&entry.name);
+                trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
+
+                let path_icon = entry
+                    .icon
+                    .as_deref()
+                    .and_then(|i| find_icon(i, &paths_data_dirs));
+                let path_game_dir = some_if_dir(app.path_active.join("files"));
+
+                trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
+                trace!("{LAUNCHER} - Icon for '{title}': {path_icon:?}");
+
+                Some(Game {
+                    title,
+                    launch_command,
+                    path_box_art: None,
+                    path_game_dir,
+                    path_icon,
+                    source: LAUNCHER.clone(),
+                    install_variant: InstallVariant::Flatpak,
+                    metadata: GameMetadata::default(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Launcher for Flatpak {
+    fn get_launcher_type(&self) -> SupportedLaunchers {
+        LAUNCHER
+    }
+
+    fn get_install_variant(&self) -> InstallVariant {
+        InstallVariant::Flatpak
+    }
+
+    fn is_detected(&self) -> bool {
+        self.paths_installations.iter().any(|p| p.is_dir())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn get_detected_games(&self) -> GamesResult {
+        let games: Vec<Game> = self
+            .get_installed_apps()
+            .iter()
+            .flat_map(|app| self.get_games(app))
+            .collect();
+
+        if games.is_empty() {
+            warn_no_games!();
+        }
+
+        Ok(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
+
+    #[test]
+    fn test_flatpak_launcher() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = Flatpak::new(
+            &path_file_system_mock.join(".local/share"),
+            &path_file_system_mock.join("var/lib/flatpak"),
+        );
+
+        assert!(launcher.is_detected());
+
+        let mut games = launcher.get_detected_games()?;
+        games.sort_by_key(|g| g.title.clone());
+
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].title, "0 A.D.");
+        assert_eq!(games[1].title, "SuperTuxKart");
+
+        assert_eq!(games[0].launch_command.get_program(), "flatpak");
+        assert_eq!(
+            games[0].launch_command.get_args().collect::<Vec<_>>(),
+            ["run", "com.play0ad.zeroad"]
+        );
+        assert_eq!(
+            games[1].launch_command.get_args().collect::<Vec<_>>(),
+            [
+                "run",
+                "--branch=stable",
+                "--command=supertuxkart",
+                "net.supertuxkart.SuperTuxKart"
+            ]
+        );
+
+        assert!(games[0].path_icon.is_none());
+        assert!(games[1].path_icon.is_some());
+
+        assert!(games[0].path_game_dir.is_none());
+        assert!(games[1].path_game_dir.is_some());
+
+        assert!(games.iter().all(|g| g.path_box_art.is_none()));
+
+        Ok(())
+    }
+}
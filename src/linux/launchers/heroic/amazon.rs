@@ -1,15 +1,13 @@
-use std::{
-    io::{self},
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use tracing::{error, trace, warn};
 
 use super::ParsableLibraryData;
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    error::GamesParsingError,
     linux::launchers::heroic::{
-        get_heroic_config_path, get_launch_command_for_heroic_source,
+        HeroicMetadata, get_heroic_config_path, get_launch_command_for_heroic_source,
         parse_all_games_from_library_common,
     },
     macros::logs::{debug_path, warn_no_games},
@@ -22,12 +20,13 @@ const LAUNCHER: SupportedLaunchers = SupportedLaunchers::HeroicGamesAmazon;
 pub struct HeroicAmazon {
     path_nile_library: PathBuf,
     path_icons: PathBuf,
-    is_using_flatpak: bool,
+    path_heroic_config: PathBuf,
+    install_variant: InstallVariant,
 }
 
 impl HeroicAmazon {
-    pub fn new(path_home: &Path, path_config: &Path) -> Self {
-        let (path_heroic_config, is_using_flatpak) = get_heroic_config_path(path_home, path_config);
+    pub fn new(path_home: &Path, path_config: &Path, install_variant: InstallVariant) -> Self {
+        let path_heroic_config = get_heroic_config_path(path_home, path_config, &install_variant);
 
         let path_nile_library = path_heroic_config.join("store_cache/nile_library.json");
         let path_icons = path_heroic_config.join("icons");
@@ -37,13 +36,14 @@ impl HeroicAmazon {
         HeroicAmazon {
             path_nile_library,
             path_icons,
-            is_using_flatpak,
+            path_heroic_config,
+            install_variant,
         }
     }
 
     /// Parse all relevant games' data from `nile_library.json`
     #[tracing::instrument]
-    fn parse_nile_library(&self) -> Result<Vec<ParsableLibraryData>, io::Error> {
+    fn parse_nile_library(&self) -> Result<Vec<ParsableLibraryData>, GamesParsingError> {
         trace!(
             "{LAUNCHER} - Parsing Nile library file at {:?}",
             self.path_nile_library
@@ -69,6 +69,10 @@ impl Launcher for HeroicAmazon {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let parsed_data = self
@@ -79,6 +83,8 @@ impl Launcher for HeroicAmazon {
             warn_no_games!();
         };
 
+        let heroic_metadata = HeroicMetadata::new(&self.path_heroic_config, LAUNCHER);
+
         Ok(parsed_data
             .into_iter()
             .map(|parsed_data| {
@@ -86,10 +92,11 @@ impl Launcher for HeroicAmazon {
                     app_id,
                     install_path,
                     title,
+                    platform,
                 } = parsed_data;
 
                 let launch_command =
-                    get_launch_command_for_heroic_source("nile", &app_id, self.is_using_flatpak);
+                    get_launch_command_for_heroic_source("nile", &app_id, &self.install_variant);
                 trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
 
                 let path_game_dir = some_if_dir(PathBuf::from(install_path));
@@ -98,6 +105,9 @@ impl Launcher for HeroicAmazon {
                 trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
                 trace!("{LAUNCHER} - Box art for '{title}': {path_box_art:?}");
 
+                let metadata = heroic_metadata.get_metadata(&app_id, platform.as_deref());
+                trace!("{LAUNCHER} - Metadata for '{title}': {metadata:?}");
+
                 Game {
                     title,
                     launch_command,
@@ -105,6 +115,8 @@ impl Launcher for HeroicAmazon {
                     path_game_dir,
                     path_icon: None,
                     source: LAUNCHER.clone(),
+                    install_variant: self.install_variant.clone(),
+                    metadata,
                 }
             })
             .collect())
@@ -118,20 +130,23 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".config"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, "", ".config"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/data/path"; "snap")]
     fn test_heroic_amazon_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_config: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = HeroicAmazon::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_config),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let games = launcher.get_detected_games()?;
 
@@ -148,6 +163,22 @@ mod tests {
 
         assert!(games.iter().all(|g| g.path_icon.is_none()));
 
+        assert_eq!(
+            games[0].metadata.path_executable,
+            Some(PathBuf::from(
+                "/home/user/Games/Heroic/Metal Slug/mslug.exe"
+            ))
+        );
+        assert_eq!(
+            games[0].metadata.launch_options.as_deref(),
+            Some("--windowed")
+        );
+        assert!(games[1].metadata.path_executable.is_none());
+
+        assert_eq!(games[0].metadata.last_played, Some(1_703_498_400));
+        assert_eq!(games[0].metadata.playtime, Some(60));
+        assert!(games[1].metadata.last_played.is_none());
+
         Ok(())
     }
 }
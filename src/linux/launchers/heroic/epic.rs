@@ -1,15 +1,13 @@
-use std::{
-    io::{self},
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use tracing::{error, trace, warn};
 
 use super::ParsableLibraryData;
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    error::GamesParsingError,
     linux::launchers::heroic::{
-        get_heroic_config_path, get_launch_command_for_heroic_source,
+        HeroicMetadata, get_heroic_config_path, get_launch_command_for_heroic_source,
         parse_all_games_from_library_common,
     },
     macros::logs::{debug_path, warn_no_games},
@@ -22,12 +20,13 @@ const LAUNCHER: SupportedLaunchers = SupportedLaunchers::HeroicGamesEpic;
 pub struct HeroicEpic {
     path_legendary_library: PathBuf,
     path_icons: PathBuf,
-    is_using_flatpak: bool,
+    path_heroic_config: PathBuf,
+    install_variant: InstallVariant,
 }
 
 impl HeroicEpic {
-    pub fn new(path_home: &Path, path_config: &Path) -> Self {
-        let (path_heroic_config, is_using_flatpak) = get_heroic_config_path(path_home, path_config);
+    pub fn new(path_home: &Path, path_config: &Path, install_variant: InstallVariant) -> Self {
+        let path_heroic_config = get_heroic_config_path(path_home, path_config, &install_variant);
 
         let path_legendary_library = path_heroic_config.join("store_cache/legendary_library.json");
         let path_icons = path_heroic_config.join("icons");
@@ -37,13 +36,14 @@ impl HeroicEpic {
         HeroicEpic {
             path_legendary_library,
             path_icons,
-            is_using_flatpak,
+            path_heroic_config,
+            install_variant,
         }
     }
 
     /// Parse all relevant games' data from `legendary_library.json`
     #[tracing::instrument(level = "trace")]
-    fn parse_legendary_library(&self) -> Result<Vec<ParsableLibraryData>, io::Error> {
+    fn parse_legendary_library(&self) -> Result<Vec<ParsableLibraryData>, GamesParsingError> {
         trace!(
             "{LAUNCHER} - Parsing Legendary library file at {:?}",
             self.path_legendary_library
@@ -69,6 +69,10 @@ impl Launcher for HeroicEpic {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let parsed_data = self.parse_legendary_library().map_err(|e| {
@@ -80,6 +84,8 @@ impl Launcher for HeroicEpic {
             warn_no_games!();
         };
 
+        let heroic_metadata = HeroicMetadata::new(&self.path_heroic_config, LAUNCHER);
+
         Ok(parsed_data
             .into_iter()
             .map(|parsed_data| {
@@ -87,12 +93,13 @@ impl Launcher for HeroicEpic {
                     app_id,
                     install_path,
                     title,
+                    platform,
                 } = parsed_data;
 
                 let launch_command = get_launch_command_for_heroic_source(
                     "legendary",
                     &app_id,
-                    self.is_using_flatpak,
+                    &self.install_variant,
                 );
                 trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
 
@@ -102,6 +109,9 @@ impl Launcher for HeroicEpic {
                 trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
                 trace!("{LAUNCHER} - Box art for '{title}': {path_box_art:?}");
 
+                let metadata = heroic_metadata.get_metadata(&app_id, platform.as_deref());
+                trace!("{LAUNCHER} - Metadata for '{title}': {metadata:?}");
+
                 Game {
                     title,
                     launch_command,
@@ -109,6 +119,8 @@ impl Launcher for HeroicEpic {
                     path_game_dir,
                     path_icon: None,
                     source: LAUNCHER.clone(),
+                    install_variant: self.install_variant.clone(),
+                    metadata,
                 }
             })
             .collect())
@@ -122,20 +134,23 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".config"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, "", ".config"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/data/path"; "snap")]
     fn test_heroic_epic_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_config: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = HeroicEpic::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_config),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let games = launcher.get_detected_games()?;
 
@@ -152,6 +167,27 @@ mod tests {
 
         assert!(games.iter().all(|g| g.path_icon.is_none()));
 
+        // Per-game settings, and the global defaults for games without any
+        assert_eq!(
+            games[0].metadata.launch_options.as_deref(),
+            Some("-nolauncher")
+        );
+        assert!(games[0].metadata.use_game_mode);
+        assert_eq!(
+            games[1]
+                .metadata
+                .compat_tool
+                .as_ref()
+                .map(|t| t.name.as_str()),
+            Some("Proton - GE-Proton9-20")
+        );
+        assert!(!games[1].metadata.use_game_mode);
+
+        assert_eq!(games[0].metadata.last_played, Some(1_710_107_130));
+        assert_eq!(games[0].metadata.playtime, Some(754));
+        assert!(games[1].metadata.last_played.is_none());
+        assert!(games[1].metadata.playtime.is_none());
+
         Ok(())
     }
 }
@@ -0,0 +1,311 @@
+// PATHS:
+// - <heroic config>/config.json (`defaultSettings`)
+// - <heroic config>/GamesConfig/<app id>.json
+use std::path::{Path, PathBuf};
+
+use tracing::{trace, warn};
+
+use crate::{
+    data::{CompatTool, GameMetadata, SupportedLaunchers},
+    json::{self, Value},
+    macros::logs::debug_path,
+    utils::some_if_dir,
+};
+
+/// Settings which can be set for each game in Heroic, or globally as the defaults for all games.
+/// Fields are [`None`] if they aren't set.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Settings {
+    /// Name of the Wine (or Proton) version, e.g. `Proton - GE-Proton9-20`
+    wine_name: Option<String>,
+    /// Path to the Wine (or Proton) executable
+    wine_bin: Option<String>,
+    wine_prefix: Option<String>,
+    environment: Option<Vec<(String, String)>>,
+    launcher_args: Option<String>,
+    use_game_mode: Option<bool>,
+    show_mangohud: Option<bool>,
+    target_exe: Option<String>,
+}
+
+impl Settings {
+    /// Parse the settings from a game's object in its `GamesConfig` file, or from `defaultSettings`
+    /// in `config.json`
+    fn parse(settings: &Value) -> Self {
+        let get_str = |key| {
+            settings
+                .get_str(key)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        let wine_version = settings.get("wineVersion");
+        let get_wine_str = |key| {
+            wine_version
+                .and_then(|v| v.get_str(key))
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+
+        // Heroic's key is misspelled
+        let environment = settings.get_array("enviromentOptions").map(|options| {
+            options
+                .iter()
+                .filter_map(|option| {
+                    let key = option.get_str("key").filter(|k| !k.is_empty())?;
+                    Some((key.to_owned(), option.get_str("value")?.to_owned()))
+                })
+                .collect()
+        });
+
+        Self {
+            wine_name: get_wine_str("name"),
+            wine_bin: get_wine_str("bin"),
+            wine_prefix: get_str("winePrefix"),
+            environment,
+            launcher_args: get_str("launcherArgs"),
+            use_game_mode: settings.get_bool("useGameMode"),
+            show_mangohud: settings.get_bool("showMangohud"),
+            target_exe: get_str("targetExe"),
+        }
+    }
+
+    /// Fill in any settings which aren't set with the given defaults
+    fn or(self, defaults: &Self) -> Self {
+        // The Wine name and executable are set together, so they shouldn't be mixed
+        let (wine_name, wine_bin) = if self.wine_name.is_some() || self.wine_bin.is_some() {
+            (self.wine_name, self.wine_bin)
+        } else {
+            (defaults.wine_name.clone(), defaults.wine_bin.clone())
+        };
+
+        Self {
+            wine_name,
+            wine_bin,
+            wine_prefix: self.wine_prefix.or_else(|| defaults.wine_prefix.clone()),
+            environment: self.environment.or_else(|| defaults.environment.clone()),
+            launcher_args: self
+                .launcher_args
+                .or_else(|| defaults.launcher_args.clone()),
+            use_game_mode: self.use_game_mode.or(defaults.use_game_mode),
+            show_mangohud: self.show_mangohud.or(defaults.show_mangohud),
+            target_exe: self.target_exe.or_else(|| defaults.target_exe.clone()),
+        }
+    }
+}
+
+/// Get the install directory of a Wine (or Proton) version from the path to its executable, e.g.
+/// `<tool>/proton` or `<tool>/bin/wine`
+fn get_wine_dir(path_wine_bin: &Path) -> Option<PathBuf> {
+    let path_parent = path_wine_bin.parent()?;
+    let path_tool = match path_parent.file_name() {
+        Some(name) if name == "bin" => path_parent.parent()?,
+        _ => path_parent,
+    };
+
+    some_if_dir(path_tool.to_path_buf())
+}
+
+/// Heroic's per-game settings, shared by all of its sources
+#[derive(Debug)]
+pub struct GamesConfig {
+    launcher: SupportedLaunchers,
+    path_games_config: PathBuf,
+    defaults: Settings,
+}
+
+impl GamesConfig {
+    /// Read the global default settings from the given Heroic config directory, for the given
+    /// Heroic source. Per-game settings are only read when requested.
+    #[tracing::instrument(level = "trace")]
+    pub fn new(path_heroic_config: &Path, launcher: SupportedLaunchers) -> Self {
+        let path_global_config = path_heroic_config.join("config.json");
+        let path_games_config = path_heroic_config.join("GamesConfig");
+
+        debug_path!(launcher, "global config JSON file", path_global_config);
+        debug_path!(launcher, "per-game config dir", path_games_config);
+
+        let defaults = if path_global_config.is_file() {
+            json::parse_file(&path_global_config)
+                .inspect_err(|e| {
+                    warn!(
+                        "{launcher} - Error parsing the global config file at {path_global_config:?}: {e}"
+                    )
+                })
+                .ok()
+                .and_then(|config| config.get("defaultSettings").map(Settings::parse))
+                .unwrap_or_default()
+        } else {
+            Settings::default()
+        };
+
+        Self {
+            launcher,
+            path_games_config,
+            defaults,
+        }
+    }
+
+    /// Get the settings of the game with the given app ID, falling back to the global defaults
+    fn get_settings(&self, app_id: &str) -> Settings {
+        let path_game_config = self.path_games_config.join(format!("{app_id}.json"));
+        if !path_game_config.is_file() {
+            return self.defaults.clone();
+        }
+
+        json::parse_file(&path_game_config)
+            .inspect_err(|e| {
+                warn!(
+                    "{} - Error parsing the game config file at {path_game_config:?}: {e}",
+                    self.launcher
+                )
+            })
+            .ok()
+            .and_then(|config| config.get(app_id).map(Settings::parse))
+            .unwrap_or_default()
+            .or(&self.defaults)
+    }
+
+    /// Get the metadata given by the settings of the game with the given app ID. The Wine version
+    /// and prefix are only used for games which don't run natively, as given by their platform.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_metadata(&self, app_id: &str, platform: Option<&str>) -> GameMetadata {
+        let settings = self.get_settings(app_id);
+        trace!("{} - Settings for '{app_id}': {settings:?}", self.launcher);
+
+        let is_native = platform.is_some_and(|p| p.eq_ignore_ascii_case("linux"));
+        let compat_tool = settings
+            .wine_name
+            .filter(|_| !is_native)
+            .map(|name| CompatTool {
+                name,
+                path_tool: settings
+                    .wine_bin
+                    .and_then(|bin| get_wine_dir(Path::new(&bin))),
+                path_prefix: settings
+                    .wine_prefix
+                    .and_then(|prefix| some_if_dir(PathBuf::from(prefix))),
+            });
+
+        GameMetadata {
+            compat_tool,
+            path_executable: settings.target_exe.map(PathBuf::from),
+            launch_options: settings.launcher_args,
+            environment: settings.environment.unwrap_or_default(),
+            use_game_mode: settings.use_game_mode.unwrap_or_default(),
+            use_mangohud: settings.show_mangohud.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
+
+    #[test]
+    fn test_settings() -> Result<(), GamesParsingError> {
+        let defaults = Settings::parse(&json::parse(
+            r#"{
+                "wineVersion": { "bin": "/tools/proton/GE-Proton9-20/proton", "name": "Proton - GE-Proton9-20", "type": "proton" },
+                "winePrefix": "/prefixes/default",
+                "enviromentOptions": [],
+                "useGameMode": false,
+                "showMangohud": true
+            }"#,
+        )?);
+        let settings = Settings::parse(&json::parse(
+            r#"{
+                "wineVersion": { "name": "Wine - Wine-GE-Proton8-26", "type": "wine" },
+                "enviromentOptions": [{ "key": "DXVK_HUD", "value": "fps" }, { "key": "", "value": "x" }],
+                "launcherArgs": "",
+                "useGameMode": true
+            }"#,
+        )?);
+
+        assert_eq!(
+            settings.or(&defaults),
+            Settings {
+                wine_name: Some(String::from("Wine - Wine-GE-Proton8-26")),
+                wine_bin: None,
+                wine_prefix: Some(String::from("/prefixes/default")),
+                environment: Some(vec![(String::from("DXVK_HUD"), String::from("fps"))]),
+                launcher_args: None,
+                use_game_mode: Some(true),
+                show_mangohud: Some(true),
+                target_exe: None,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test_case(
+        "tools/wine/Wine-GE-Proton8-26/bin/wine",
+        Some("tools/wine/Wine-GE-Proton8-26")
+    )]
+    #[test_case(
+        "tools/proton/GE-Proton9-20/proton",
+        Some("tools/proton/GE-Proton9-20")
+    )]
+    #[test_case("tools/proton/not_installed/proton", None)]
+    fn test_get_wine_dir(path_wine_bin: &str, expected: Option<&str>) {
+        let path_heroic_config = get_mock_file_system_path().join(".config/heroic");
+
+        assert_eq!(
+            get_wine_dir(&path_heroic_config.join(path_wine_bin)),
+            expected.map(|p| path_heroic_config.join(p))
+        );
+    }
+
+    #[test]
+    fn test_games_config() {
+        let games_config = GamesConfig::new(
+            &get_mock_file_system_path().join(".config/heroic"),
+            SupportedLaunchers::HeroicGamesEpic,
+        );
+
+        // Per-game settings
+        let metadata =
+            games_config.get_metadata("0a2d9f6403244d12969e11da6713137b", Some("Windows"));
+        assert_eq!(
+            metadata.compat_tool.map(|t| t.name).as_deref(),
+            Some("Wine - Wine-GE-Proton8-26")
+        );
+        assert_eq!(
+            metadata.environment,
+            [(String::from("DXVK_HUD"), String::from("fps"))]
+        );
+        assert_eq!(metadata.launch_options.as_deref(), Some("-nolauncher"));
+        assert!(metadata.use_game_mode);
+        assert!(metadata.use_mangohud);
+        assert!(metadata.path_executable.is_none());
+
+        // Global defaults
+        let metadata = games_config.get_metadata("not_configured", None);
+        assert_eq!(
+            metadata.compat_tool.map(|t| t.name).as_deref(),
+            Some("Proton - GE-Proton9-20")
+        );
+        assert!(metadata.environment.is_empty());
+        assert!(!metadata.use_game_mode);
+        assert!(!metadata.use_mangohud);
+
+        // No compatibility tool for native games
+        let metadata = games_config.get_metadata("1207664543", Some("linux"));
+        assert!(metadata.compat_tool.is_none());
+        assert!(metadata.use_game_mode);
+
+        // No config files
+        let games_config = GamesConfig::new(
+            &get_mock_file_system_path().join("invalid/path"),
+            SupportedLaunchers::HeroicGamesEpic,
+        );
+        assert_eq!(
+            games_config.get_metadata("1207664543", Some("windows")),
+            GameMetadata::default()
+        );
+    }
+}
@@ -1,26 +1,39 @@
 use std::{
-    io::{self},
+    collections::HashMap,
     path::{Path, PathBuf},
 };
 
-use nom::IResult;
 use tracing::{error, trace, warn};
 
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    error::GamesParsingError,
+    json::Value,
     linux::launchers::heroic::{
-        get_heroic_config_path, get_launch_command_for_heroic_source, parse_all_games_from_library,
+        HeroicMetadata, get_cached_image_path, get_heroic_config_path,
+        get_launch_command_for_heroic_source, parse_all_games_from_library,
     },
     macros::logs::{debug_path, warn_no_games},
-    parsers::parse_value_json,
     utils::{clean_game_title, some_if_dir, some_if_file},
 };
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 struct ParsableGOGInstalledData {
     app_id: String,
     install_path: String,
+    platform: Option<String>,
+}
+
+/// Data about a game from the GOG library cache, `store_cache/gog_library.json`, which lists all
+/// owned games (installed or not). Art is given as URLs, which Heroic caches locally.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ParsableGOGLibraryData {
+    app_id: String,
     title: String,
+    art_square: Option<String>,
+    art_cover: Option<String>,
+    art_logo: Option<String>,
+    art_background: Option<String>,
 }
 
 const LAUNCHER: SupportedLaunchers = SupportedLaunchers::HeroicGamesGOG;
@@ -30,64 +43,82 @@ const LAUNCHER: SupportedLaunchers = SupportedLaunchers::HeroicGamesGOG;
 /// Unfortunately a separate parser function is needed for GOG's `gog_store/installed.json` file because:
 /// 1. `store_cache/gog_library.json` has `is_installed` as always false
 /// 2. `gog_store/library.json` is empty for some reason
-#[tracing::instrument(level = "trace", skip(file_content))]
-fn parse_game_from_gog_installed(file_content: &str) -> IResult<&str, ParsableGOGInstalledData> {
-    // INSTALL_PATH
-    let (file_content, install_path) = parse_value_json(file_content, "install_path")?;
-
-    // ID
-    let (file_content, app_id) = parse_value_json(file_content, "appName")?;
-
-    // TITLE
-    let Some(title) = install_path
-        .rsplit_once('/')
-        .map(|split_path| clean_game_title(split_path.1))
-    else {
-        return parse_game_from_gog_installed(file_content);
+#[tracing::instrument(level = "trace", skip(game))]
+fn parse_game_from_gog_installed(game: &Value) -> Option<ParsableGOGInstalledData> {
+    Some(ParsableGOGInstalledData {
+        app_id: game.get_str("appName")?.to_owned(),
+        install_path: game.get_str("install_path")?.to_owned(),
+        platform: game.get_str("platform").map(String::from),
+    })
+}
+
+/// Utility function which parses a single game from the Heroic Games GOG `gog_library.json` file
+#[tracing::instrument(level = "trace", skip(game))]
+fn parse_game_from_gog_library(game: &Value) -> Option<ParsableGOGLibraryData> {
+    let get_url = |key| {
+        game.get_str(key)
+            .filter(|url| !url.is_empty())
+            .map(String::from)
     };
 
-    Ok((
-        file_content,
-        ParsableGOGInstalledData {
-            app_id,
-            title: clean_game_title(title),
-            install_path,
-        },
-    ))
+    Some(ParsableGOGLibraryData {
+        app_id: game.get_str("app_name")?.to_owned(),
+        title: clean_game_title(game.get_str("title")?),
+        art_square: get_url("art_square"),
+        art_cover: get_url("art_cover"),
+        art_logo: get_url("art_logo"),
+        art_background: get_url("art_background"),
+    })
+}
+
+/// Get a game's title from its install path, for games missing from the library cache
+fn get_title_from_install_path(install_path: &str) -> Option<String> {
+    let (_, title) = install_path.rsplit_once('/')?;
+    Some(clean_game_title(title))
 }
 
 #[derive(Debug)]
 pub struct HeroicGOG {
     path_gog_installed_games: PathBuf,
+    path_gog_library: PathBuf,
     path_icons: PathBuf,
-    is_using_flatpak: bool,
+    path_images_cache: PathBuf,
+    path_heroic_config: PathBuf,
+    install_variant: InstallVariant,
 }
 
 impl HeroicGOG {
-    pub fn new(path_home: &Path, path_config: &Path) -> Self {
-        let (path_heroic_config, is_using_flatpak) = get_heroic_config_path(path_home, path_config);
+    pub fn new(path_home: &Path, path_config: &Path, install_variant: InstallVariant) -> Self {
+        let path_heroic_config = get_heroic_config_path(path_home, path_config, &install_variant);
         let path_gog_installed_games = path_heroic_config.join("gog_store/installed.json");
+        let path_gog_library = path_heroic_config.join("store_cache/gog_library.json");
         let path_icons = path_heroic_config.join("icons");
+        let path_images_cache = path_heroic_config.join("images-cache");
 
         debug_path!("installed games JSON file", path_gog_installed_games);
+        debug_path!("library cache JSON file", path_gog_library);
 
         HeroicGOG {
             path_gog_installed_games,
+            path_gog_library,
             path_icons,
-            is_using_flatpak,
+            path_images_cache,
+            path_heroic_config,
+            install_variant,
         }
     }
 
     /// Parse all relevant games' data from GOG's `installed.json`
     #[tracing::instrument]
-    fn parse_gog_installed(&self) -> Result<Vec<ParsableGOGInstalledData>, io::Error> {
+    fn parse_gog_installed(&self) -> Result<Vec<ParsableGOGInstalledData>, GamesParsingError> {
         trace!(
             "Parsing Heroic Launcher GOG installed games file at {:?}",
             self.path_gog_installed_games
         );
 
-        parse_all_games_from_library::<ParsableGOGInstalledData>(
+        parse_all_games_from_library(
             &self.path_gog_installed_games,
+            "installed",
             parse_game_from_gog_installed,
         )
         .inspect(|data| {
@@ -99,6 +130,28 @@ impl HeroicGOG {
             };
         })
     }
+
+    /// Parse all games' data from GOG's `gog_library.json`, mapped by app ID. Errors are only
+    /// logged, as the library cache is only used to enrich the data of installed games.
+    #[tracing::instrument]
+    fn parse_gog_library(&self) -> HashMap<String, ParsableGOGLibraryData> {
+        trace!(
+            "Parsing Heroic Launcher GOG library cache file at {:?}",
+            self.path_gog_library
+        );
+
+        parse_all_games_from_library(&self.path_gog_library, "games", parse_game_from_gog_library)
+            .inspect_err(|e| {
+                warn!(
+                    "{LAUNCHER} - Error parsing the GOG library cache file at {:?}: {e}",
+                    self.path_gog_library
+                )
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|data| (data.app_id.clone(), data))
+            .collect()
+    }
 }
 
 impl Launcher for HeroicGOG {
@@ -110,6 +163,10 @@ impl Launcher for HeroicGOG {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let parsed_data = self.parse_gog_installed().map_err(|e| {
@@ -121,33 +178,65 @@ impl Launcher for HeroicGOG {
             warn_no_games!();
         };
 
+        let mut library = self.parse_gog_library();
+        let heroic_metadata = HeroicMetadata::new(&self.path_heroic_config, LAUNCHER);
+
         Ok(parsed_data
             .into_iter()
-            .map(|parsed_data| {
+            .filter_map(|parsed_data| {
                 let ParsableGOGInstalledData {
                     app_id,
                     install_path,
-                    title,
+                    platform,
                 } = parsed_data;
+                let library_data = library.remove(&app_id);
+
+                let title = match &library_data {
+                    Some(data) => data.title.clone(),
+                    None => get_title_from_install_path(&install_path)?,
+                };
 
                 let launch_command =
-                    get_launch_command_for_heroic_source("gog", &app_id, self.is_using_flatpak);
+                    get_launch_command_for_heroic_source("gog", &app_id, &self.install_variant);
                 trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
 
                 let path_game_dir = some_if_dir(PathBuf::from(install_path));
                 let path_icon = some_if_file(self.path_icons.join(format!("{app_id}.png")));
 
+                let get_cached_image =
+                    |url: Option<String>| get_cached_image_path(&self.path_images_cache, &url?);
+                let ParsableGOGLibraryData {
+                    art_square,
+                    art_cover,
+                    art_logo,
+                    art_background,
+                    ..
+                } = library_data.unwrap_or_default();
+                // Only the square art is portrait box art, `art_cover` is a landscape banner
+                let path_box_art = get_cached_image(art_square);
+
                 trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
                 trace!("{LAUNCHER} - Icon for '{title}': {path_icon:?}");
+                trace!("{LAUNCHER} - Box art for '{title}': {path_box_art:?}");
+
+                let metadata = GameMetadata {
+                    path_cover: get_cached_image(art_cover),
+                    path_logo: get_cached_image(art_logo),
+                    path_background: get_cached_image(art_background),
+                    ..heroic_metadata.get_metadata(&app_id, platform.as_deref())
+                };
+                trace!("{LAUNCHER} - Metadata for '{title}': {metadata:?}");
 
-                Game {
+                Some(Game {
                     title,
                     launch_command,
                     path_game_dir,
                     path_icon,
-                    path_box_art: None,
+                    path_box_art,
                     source: LAUNCHER.clone(),
-                }
+                    install_variant: self.install_variant.clone(),
+                    metadata,
+                })
             })
             .collect())
     }
@@ -158,28 +247,84 @@ mod tests {
     use test_case::test_case;
 
     use super::*;
-    use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
+    use crate::{json, linux::test_utils::get_mock_file_system_path};
+
+    #[test]
+    fn test_parse_game_from_gog_installed() -> Result<(), GamesParsingError> {
+        let game = json::parse(
+            r#"{ "appName": "1207664543", "versionEtag": "\"2557448440\"", "install_path": "/games/Gwent™", "platform": "windows" }"#,
+        )?;
+        assert_eq!(
+            parse_game_from_gog_installed(&game),
+            Some(ParsableGOGInstalledData {
+                app_id: String::from("1207664543"),
+                install_path: String::from("/games/Gwent™"),
+                platform: Some(String::from("windows")),
+            })
+        );
+
+        let game = json::parse(r#"{ "appName": "1207664543" }"#)?;
+        assert!(parse_game_from_gog_installed(&game).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_game_from_gog_library() -> Result<(), GamesParsingError> {
+        let game = json::parse(
+            r#"{ "app_name": "1207664543", "title": "GWENT™", "art_square": "", "art_cover": "https://images.gog.com/gwent.jpg", "art_logo": null, "is_installed": false }"#,
+        )?;
+        assert_eq!(
+            parse_game_from_gog_library(&game),
+            Some(ParsableGOGLibraryData {
+                app_id: String::from("1207664543"),
+                title: String::from("GWENT"),
+                art_square: None,
+                art_cover: Some(String::from("https://images.gog.com/gwent.jpg")),
+                art_logo: None,
+                art_background: None,
+            })
+        );
+
+        let game = json::parse(r#"{ "app_name": "1207664543" }"#)?;
+        assert!(parse_game_from_gog_library(&game).is_none());
 
-    #[test_case(false, ".config"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+        Ok(())
+    }
+
+    #[test_case("/games/Gwent™", Some("Gwent"))]
+    #[test_case("Gwent", None)]
+    fn test_get_title_from_install_path(install_path: &str, expected: Option<&str>) {
+        assert_eq!(
+            get_title_from_install_path(install_path).as_deref(),
+            expected
+        );
+    }
+
+    #[test_case(InstallVariant::Native, "", ".config"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/data/path"; "snap")]
     fn test_heroic_gog_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_config: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = HeroicGOG::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_config),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let games = launcher.get_detected_games()?;
 
         assert_eq!(games.len(), 2);
 
-        assert_eq!(games[0].title, "home");
+        // Titles come from the library cache rather than the install path
+        assert_eq!(games[0].title, "GWENT: The Witcher Card Game");
         assert_eq!(games[1].title, "Bread & Fred Demo");
 
         assert!(games[0].path_game_dir.is_some());
@@ -188,7 +333,42 @@ mod tests {
         assert!(games[0].path_icon.is_none());
         assert!(games[1].path_icon.is_some());
 
-        assert!(games.iter().all(|g| g.path_box_art.is_none()));
+        // Only Bread & Fred's box art has been cached
+        assert!(games[0].path_box_art.is_none());
+        assert!(
+            games[1]
+                .path_box_art
+                .as_ref()
+                .is_some_and(|p| p.parent().is_some_and(|p| p.ends_with("images-cache")))
+        );
+
+        // Other art is given in the metadata, where it has been cached
+        let is_cached = |path: &Option<PathBuf>| {
+            path.as_ref()
+                .is_some_and(|p| p.parent().is_some_and(|p| p.ends_with("images-cache")))
+        };
+        assert!(is_cached(&games[0].metadata.path_cover));
+        assert!(is_cached(&games[0].metadata.path_logo));
+        assert!(is_cached(&games[0].metadata.path_background));
+        assert!(games[1].metadata.path_cover.is_none());
+        assert!(games[1].metadata.path_logo.is_none());
+        assert!(is_cached(&games[1].metadata.path_background));
+
+        // The default Wine version isn't used for the native Linux game
+        assert!(games[0].metadata.compat_tool.is_none());
+        assert!(games[0].metadata.use_game_mode);
+        assert_eq!(
+            games[1]
+                .metadata
+                .compat_tool
+                .as_ref()
+                .map(|t| t.name.as_str()),
+            Some("Proton - GE-Proton9-20")
+        );
+
+        assert!(games[0].metadata.last_played.is_none());
+        assert_eq!(games[1].metadata.last_played, Some(1_704_164_645));
+        assert_eq!(games[1].metadata.playtime, Some(24));
 
         Ok(())
     }
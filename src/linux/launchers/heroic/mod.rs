@@ -1,123 +1,224 @@
 // PATHS:
 // - ~/.config/heroic/
+// - Flatpak: ~/.var/app/com.heroicgameslauncher.hgl/config/heroic/
+// - Snap: ~/snap/heroic/common/.config/heroic/
 pub mod amazon;
 pub mod epic;
+mod games_config;
 pub mod gog;
 pub mod sideload;
+mod timestamps;
 
 use std::{
-    fs::read_to_string,
-    io,
     path::{Path, PathBuf},
     process::Command,
 };
 
-use nom::IResult;
-use tracing::debug;
+use games_config::GamesConfig;
+use sha2::{Digest, Sha256};
+use timestamps::{PlayTime, Timestamps};
 
 use crate::{
-    parsers::{parse_value_json, parse_value_json_unquoted},
-    utils::{clean_game_title, get_launch_command, get_launch_command_flatpak},
+    data::{GameMetadata, InstallVariant, SupportedLaunchers},
+    error::GamesParsingError,
+    json::{self, Value},
+    utils::{
+        clean_game_title, get_launch_command, get_launch_command_flatpak, get_launch_command_snap,
+        some_if_file,
+    },
 };
 
 /// Useful data about a game which is parseable from a Heroic Games Launcher library file
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 struct ParsableLibraryData {
     app_id: String,
     install_path: String,
     title: String,
+    /// Platform of the installed version of the game, e.g. `Windows`
+    platform: Option<String>,
 }
 
-/// Parses a single (installed) game from a Heroic Games Launcher library file
-#[tracing::instrument(level = "trace", skip(file_content))]
-fn parse_game_from_library_common(file_content: &str) -> IResult<&str, ParsableLibraryData> {
-    // ID
-    let (file_content, app_id) = parse_value_json(file_content, "app_name")?;
-
-    // Keep checkpoint of file content because `is_installed` comes after the `install_path`
-    // and `title` may come before install info
-    let file_content_checkpoint = file_content;
+/// Metadata of games from Heroic's per-game settings and play times, shared by all of its sources
+#[derive(Debug)]
+struct HeroicMetadata {
+    games_config: GamesConfig,
+    timestamps: Timestamps,
+}
 
-    // IS_INSTALLED
-    let (file_content, is_installed) = parse_value_json_unquoted(file_content, "is_installed")?;
+impl HeroicMetadata {
+    /// Read the settings and play times of games from the given Heroic config directory, for the
+    /// given Heroic source
+    fn new(path_heroic_config: &Path, launcher: SupportedLaunchers) -> Self {
+        Self {
+            games_config: GamesConfig::new(path_heroic_config, launcher.clone()),
+            timestamps: Timestamps::new(path_heroic_config, launcher),
+        }
+    }
 
-    // Continue to next game if not installed
-    if is_installed == *"false" {
-        return parse_game_from_library_common(file_content);
+    /// Get the metadata of the game with the given app ID, from its settings (depending on the
+    /// platform of its installed version) and its play time
+    fn get_metadata(&self, app_id: &str, platform: Option<&str>) -> GameMetadata {
+        let PlayTime {
+            last_played,
+            playtime,
+        } = self.timestamps.get_play_time(app_id);
+
+        GameMetadata {
+            last_played,
+            playtime,
+            ..self.games_config.get_metadata(app_id, platform)
+        }
     }
+}
 
-    // INSTALL_PATH
-    let (file_content, install_path) = parse_value_json(file_content_checkpoint, "install_path")?;
+/// Parses a single (installed) game from the Legendary or Nile library file, where each game's
+/// install info is nested in its `install` object
+#[tracing::instrument(level = "trace", skip(game))]
+fn parse_game_from_library_common(game: &Value) -> Option<ParsableLibraryData> {
+    if game.get_bool("is_installed") != Some(true) {
+        return None;
+    }
 
-    // TITLE
-    let (file_content, title) = parse_value_json(file_content, "title")?;
+    let install = game.get("install")?;
 
-    Ok((
-        file_content,
-        ParsableLibraryData {
-            app_id,
-            title: clean_game_title(title),
-            install_path,
-        },
-    ))
+    Some(ParsableLibraryData {
+        app_id: game.get_str("app_name")?.to_owned(),
+        install_path: install.get_str("install_path")?.to_owned(),
+        title: clean_game_title(game.get_str("title")?),
+        platform: install.get_str("platform").map(String::from),
+    })
 }
 
-/// Parses all (installed) games from a given Heroic Games Launcher library file
-#[tracing::instrument]
+/// Parses all games listed under the given key of a Heroic Games Launcher library file, skipping
+/// those which the given function can't parse (e.g. because they aren't installed)
+#[tracing::instrument(skip(parse_fn))]
 fn parse_all_games_from_library<T>(
     path_library: &Path,
-    parse_fn: fn(file_content: &str) -> IResult<&str, T>,
-) -> Result<Vec<T>, io::Error> {
-    let mut parsed_data = Vec::new();
-
-    let file_content = read_to_string(path_library)?;
-    let mut file_content_str: &str = &file_content;
-
-    // Parse individual games from library file until no more are found
-    loop {
-        let Ok((new_file_content, parsed_game_data)) = parse_fn(file_content_str) else {
-            break;
-        };
-
-        file_content_str = new_file_content;
-        parsed_data.push(parsed_game_data);
-    }
-
-    Ok(parsed_data)
+    key: &str,
+    parse_fn: fn(game: &Value) -> Option<T>,
+) -> Result<Vec<T>, GamesParsingError> {
+    let library = json::parse_file(path_library)?;
+
+    Ok(library
+        .get_array(key)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(parse_fn)
+        .collect())
 }
 
+/// Parses all (installed) games from the Legendary or Nile library file
 #[tracing::instrument]
-fn parse_all_games_from_library_common(path: &Path) -> Result<Vec<ParsableLibraryData>, io::Error> {
-    parse_all_games_from_library::<ParsableLibraryData>(path, parse_game_from_library_common)
+fn parse_all_games_from_library_common(
+    path: &Path,
+) -> Result<Vec<ParsableLibraryData>, GamesParsingError> {
+    parse_all_games_from_library(path, "library", parse_game_from_library_common)
 }
 
-/// Get path to the Heroic Games Launcher config dir, falling back to the flatpak version if necessary
-fn get_heroic_config_path(path_home: &Path, path_config: &Path) -> (PathBuf, bool) {
-    let mut is_using_flatpak = false;
-    let mut path_heroic_config = path_config.join("heroic");
-
-    if !path_heroic_config.is_dir() {
-        debug!("Heroic - Attempting to fall back to flatpak");
-
-        is_using_flatpak = true;
-        path_heroic_config = path_home.join(".var/app/com.heroicgameslauncher.hgl/config/heroic");
+/// Get path to the Heroic Games Launcher config dir for the given install variant
+fn get_heroic_config_path(
+    path_home: &Path,
+    path_config: &Path,
+    install_variant: &InstallVariant,
+) -> PathBuf {
+    match install_variant {
+        InstallVariant::Flatpak => {
+            path_home.join(".var/app/com.heroicgameslauncher.hgl/config/heroic")
+        }
+        InstallVariant::Snap => path_home.join("snap/heroic/common/.config/heroic"),
+        InstallVariant::Native | InstallVariant::AppImage(_) | InstallVariant::Custom(_) => {
+            path_config.join("heroic")
+        }
     }
+}
+
+/// Get the path to Heroic's locally cached copy of the image at the given URL, if it has been
+/// cached. Heroic names each file in its `images-cache` dir after the SHA-256 hash of the URL.
+fn get_cached_image_path(path_images_cache: &Path, url: &str) -> Option<PathBuf> {
+    let file_name: String = Sha256::digest(url)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
 
-    (path_heroic_config, is_using_flatpak)
+    some_if_file(path_images_cache.join(file_name))
 }
 
 /// Get launch command for game from any of the Heroic Games Launcher sources
 fn get_launch_command_for_heroic_source(
     source: &str,
     app_id: &str,
-    is_using_flatpak: bool,
+    install_variant: &InstallVariant,
 ) -> Command {
     let game_run_arg = format!("heroic://launch/{source}/{app_id}");
     let args = [game_run_arg.as_str()];
 
-    if is_using_flatpak {
-        get_launch_command_flatpak("com.heroicgameslauncher.hgl", [], args, [])
-    } else {
-        get_launch_command("xdg-open", args, [])
+    match install_variant {
+        InstallVariant::Native => get_launch_command("xdg-open", args, []),
+        InstallVariant::Flatpak => {
+            get_launch_command_flatpak("com.heroicgameslauncher.hgl", [], args, [])
+        }
+        InstallVariant::Snap => get_launch_command_snap("heroic", args, []),
+        InstallVariant::AppImage(path) | InstallVariant::Custom(path) => {
+            get_launch_command(path, args, [])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test]
+    fn test_parse_game_from_library_common() -> Result<(), GamesParsingError> {
+        // Keys in any order, escaped quotes and the same key in nested objects
+        let game = json::parse(
+            r#"{
+                "title": "The \"Game\"™",
+                "extra": { "about": { "title": "Not the title", "is_installed": false } },
+                "is_installed": true,
+                "install": { "install_path": "/games/The Game", "is_dlc": false, "platform": "Windows" },
+                "app_name": "abc123"
+            }"#,
+        )?;
+        assert_eq!(
+            parse_game_from_library_common(&game),
+            Some(ParsableLibraryData {
+                app_id: String::from("abc123"),
+                install_path: String::from("/games/The Game"),
+                title: String::from("The \"Game\""),
+                platform: Some(String::from("Windows")),
+            })
+        );
+
+        let game = json::parse(
+            r#"{ "app_name": "abc123", "title": "Game", "install": {}, "is_installed": false }"#,
+        )?;
+        assert!(parse_game_from_library_common(&game).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_cached_image_path() {
+        let path_images_cache = get_mock_file_system_path().join(".config/heroic/images-cache");
+
+        assert_eq!(
+            get_cached_image_path(
+                &path_images_cache,
+                "https://images.gog.com/bread_and_fred_square_196.jpg"
+            ),
+            Some(
+                path_images_cache
+                    .join("7aa5f88f3aa284df26758e1a48f16c31e2bd64a92fe876eee0b01d657bf0f7db")
+            )
+        );
+        assert!(
+            get_cached_image_path(
+                &path_images_cache,
+                "https://images.gog.com/unreal_square_196.jpg"
+            )
+            .is_none()
+        );
     }
 }
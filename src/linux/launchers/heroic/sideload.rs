@@ -1,71 +1,56 @@
-use std::{
-    io,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
-use nom::IResult;
 use tracing::{error, trace, warn};
 
 use super::ParsableLibraryData;
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    error::GamesParsingError,
+    json::Value,
     linux::launchers::heroic::{
-        get_heroic_config_path, get_launch_command_for_heroic_source, parse_all_games_from_library,
+        HeroicMetadata, get_heroic_config_path, get_launch_command_for_heroic_source,
+        parse_all_games_from_library,
     },
     macros::logs::{debug_path, warn_no_games},
-    parsers::{parse_value_json, parse_value_json_unquoted},
     utils::{clean_game_title, some_if_dir, some_if_file},
 };
 
 const LAUNCHER: SupportedLaunchers = SupportedLaunchers::HeroicGamesSideload;
 
-/// Utility function which parses a single game from the Heroic Games side-load apps `library.json` file
+/// Utility function which parses a single (installed) game from the Heroic Games side-load apps
+/// `library.json` file
 ///
-/// A separate parser function is necessary for this library file because the required fields are
-/// listed in a different order to the Nile and Legendary library files.
-#[tracing::instrument(level = "trace", skip(file_content))]
-fn parse_game_from_sideload_library(file_content: &str) -> IResult<&str, ParsableLibraryData> {
-    // ID
-    let (file_content, app_id) = parse_value_json(file_content, "app_name")?;
-
-    // Keep checkpoint of file content because `is_installed` comes after the `install_path`
-    // and `title` may come before install info
-    let file_content_checkpoint = file_content;
-
-    // IS_INSTALLED
-    let (_, is_installed) = parse_value_json_unquoted(file_content, "is_installed")?;
-
-    // Continue to next game if not installed
-    if is_installed == *"false" {
-        return parse_game_from_sideload_library(file_content);
+/// A separate parser function is necessary for this library file because the install path is
+/// given by `folder_name`, rather than by `install_path` in the game's `install` object as in the
+/// Nile and Legendary library files.
+#[tracing::instrument(level = "trace", skip(game))]
+fn parse_game_from_sideload_library(game: &Value) -> Option<ParsableLibraryData> {
+    if game.get_bool("is_installed") != Some(true) {
+        return None;
     }
 
-    // TITLE
-    let (file_content, title) = parse_value_json(file_content_checkpoint, "title")?;
-
-    // INSTALL_PATH
-    let (file_content, install_path) = parse_value_json(file_content, "folder_name")?;
-
-    Ok((
-        file_content,
-        ParsableLibraryData {
-            app_id,
-            title: clean_game_title(title),
-            install_path,
-        },
-    ))
+    Some(ParsableLibraryData {
+        app_id: game.get_str("app_name")?.to_owned(),
+        install_path: game.get_str("folder_name")?.to_owned(),
+        title: clean_game_title(game.get_str("title")?),
+        platform: game
+            .get("install")
+            .and_then(|install| install.get_str("platform"))
+            .map(String::from),
+    })
 }
 
 #[derive(Debug)]
 pub struct HeroicSideload {
     path_sideload_library: PathBuf,
     path_icons: PathBuf,
-    is_using_flatpak: bool,
+    path_heroic_config: PathBuf,
+    install_variant: InstallVariant,
 }
 
 impl HeroicSideload {
-    pub fn new(path_home: &Path, path_config: &Path) -> Self {
-        let (path_heroic_config, is_using_flatpak) = get_heroic_config_path(path_home, path_config);
+    pub fn new(path_home: &Path, path_config: &Path, install_variant: InstallVariant) -> Self {
+        let path_heroic_config = get_heroic_config_path(path_home, path_config, &install_variant);
 
         let path_sideload_library = path_heroic_config.join("sideload_apps/library.json");
         let path_icons = path_heroic_config.join("icons");
@@ -75,13 +60,14 @@ impl HeroicSideload {
         Self {
             path_sideload_library,
             path_icons,
-            is_using_flatpak,
+            path_heroic_config,
+            install_variant,
         }
     }
 
     /// Parse all relevant games' data from `library.json`
     #[tracing::instrument(level = "trace")]
-    fn parse_sideload_library(&self) -> Result<Vec<ParsableLibraryData>, io::Error> {
+    fn parse_sideload_library(&self) -> Result<Vec<ParsableLibraryData>, GamesParsingError> {
         trace!(
             "{LAUNCHER} - Parsing sideload library file at {:?}",
             self.path_sideload_library
@@ -89,6 +75,7 @@ impl HeroicSideload {
 
         parse_all_games_from_library(
             &self.path_sideload_library,
+            "games",
             parse_game_from_sideload_library,
         )
         .inspect(|data| {
@@ -111,6 +98,10 @@ impl Launcher for HeroicSideload {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let parsed_data = self.parse_sideload_library().map_err(|e| {
@@ -122,6 +113,8 @@ impl Launcher for HeroicSideload {
             warn_no_games!();
         };
 
+        let heroic_metadata = HeroicMetadata::new(&self.path_heroic_config, LAUNCHER);
+
         Ok(parsed_data
             .into_iter()
             .map(|parsed_data| {
@@ -129,12 +122,13 @@ impl Launcher for HeroicSideload {
                     app_id,
                     install_path,
                     title,
+                    platform,
                 } = parsed_data;
 
                 let launch_command = get_launch_command_for_heroic_source(
                     "sideload",
                     &app_id,
-                    self.is_using_flatpak,
+                    &self.install_variant,
                 );
                 trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
 
@@ -144,6 +138,9 @@ impl Launcher for HeroicSideload {
                 trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
                 trace!("{LAUNCHER} - Box art for '{title}': {path_box_art:?}");
 
+                let metadata = heroic_metadata.get_metadata(&app_id, platform.as_deref());
+                trace!("{LAUNCHER} - Metadata for '{title}': {metadata:?}");
+
                 Game {
                     title,
                     launch_command,
@@ -151,6 +148,8 @@ impl Launcher for HeroicSideload {
                     path_game_dir,
                     path_icon: None,
                     source: LAUNCHER.clone(),
+                    install_variant: self.install_variant.clone(),
+                    metadata,
                 }
             })
             .collect())
@@ -164,20 +163,23 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".config"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, "", ".config"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/data/path"; "snap")]
     fn test_heroic_epic_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_config: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = HeroicSideload::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_config),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let games = launcher.get_detected_games()?;
 
@@ -192,6 +194,18 @@ mod tests {
         assert!(games[0].path_box_art.is_some());
         assert!(games[1].path_box_art.is_some());
 
+        assert!(games[0].metadata.use_mangohud);
+        assert_eq!(
+            games[0].metadata.environment,
+            [(String::from("MANGOHUD_CONFIG"), String::from("fps_only"))]
+        );
+        assert!(!games[1].metadata.use_mangohud);
+
+        assert_eq!(games[0].metadata.last_played, Some(1_709_247_599));
+        assert_eq!(games[0].metadata.playtime, Some(95));
+        // Invalid last played date
+        assert!(games[1].metadata.last_played.is_none());
+
         Ok(())
     }
 }
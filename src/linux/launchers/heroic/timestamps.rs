@@ -0,0 +1,117 @@
+// PATHS:
+// - <heroic config>/store/timestamp.json
+use std::{collections::HashMap, path::Path};
+
+use tracing::{trace, warn};
+
+use crate::{
+    data::SupportedLaunchers, json, macros::logs::debug_path, parsers::parse_iso_8601_timestamp,
+};
+
+/// When a game was last played, and for how long in total
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PlayTime {
+    /// When the game was last played, as a Unix timestamp in seconds
+    pub last_played: Option<u64>,
+    /// Total time the game has been played for, in minutes
+    pub playtime: Option<u64>,
+}
+
+/// Play times of all games which have been launched through Heroic, by app ID
+#[derive(Debug, Default)]
+pub struct Timestamps {
+    play_times: HashMap<String, PlayTime>,
+}
+
+impl Timestamps {
+    /// Read the play times of all games from the given Heroic config directory, for the given
+    /// Heroic source
+    #[tracing::instrument(level = "trace")]
+    pub fn new(path_heroic_config: &Path, launcher: SupportedLaunchers) -> Self {
+        let path_timestamps = path_heroic_config.join("store/timestamp.json");
+
+        debug_path!(launcher, "timestamps JSON file", path_timestamps);
+
+        if !path_timestamps.is_file() {
+            return Self::default();
+        }
+
+        let Ok(timestamps) = json::parse_file(&path_timestamps).inspect_err(|e| {
+            warn!("{launcher} - Error parsing the timestamps file at {path_timestamps:?}: {e}")
+        }) else {
+            return Self::default();
+        };
+
+        let play_times: HashMap<_, _> = timestamps
+            .as_object()
+            .unwrap_or_default()
+            .iter()
+            .map(|(app_id, times)| {
+                let last_played = times
+                    .get_str("lastPlayed")
+                    .and_then(|date| parse_iso_8601_timestamp(date).ok())
+                    .map(|(_, timestamp)| timestamp);
+
+                let play_time = PlayTime {
+                    last_played,
+                    playtime: times.get_u64("totalPlayed"),
+                };
+
+                (app_id.clone(), play_time)
+            })
+            .collect();
+
+        trace!("{launcher} - Play times of {} games", play_times.len());
+
+        Self { play_times }
+    }
+
+    /// Get the play time of the game with the given app ID
+    pub fn get_play_time(&self, app_id: &str) -> PlayTime {
+        self.play_times.get(app_id).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test]
+    fn test_timestamps() {
+        let timestamps = Timestamps::new(
+            &get_mock_file_system_path().join(".config/heroic"),
+            SupportedLaunchers::HeroicGamesGOG,
+        );
+
+        assert_eq!(
+            timestamps.get_play_time("0a2d9f6403244d12969e11da6713137b"),
+            PlayTime {
+                last_played: Some(1_710_107_130),
+                playtime: Some(754),
+            }
+        );
+        assert_eq!(
+            timestamps.get_play_time("6wMcNXN3yaVjBsu9gTaHNJ"),
+            PlayTime {
+                last_played: Some(1_709_247_599),
+                playtime: Some(95),
+            }
+        );
+        // Invalid dates and missing values are skipped
+        assert_eq!(
+            timestamps.get_play_time("7Wyx5mVRMznq9NMsrvSfPi"),
+            PlayTime::default()
+        );
+        assert_eq!(timestamps.get_play_time("not_played"), PlayTime::default());
+
+        let timestamps = Timestamps::new(
+            &get_mock_file_system_path().join("invalid/path"),
+            SupportedLaunchers::HeroicGamesGOG,
+        );
+        assert_eq!(
+            timestamps.get_play_time("0a2d9f6403244d12969e11da6713137b"),
+            PlayTime::default()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 // PATHS:
 // - ~/.config/itch/db/butler.db
 // - ~/.var/app/io.itch.itch/config/itch/db/butler.db
+// - ~/snap/itch/common/.config/itch/db/butler.db
 use std::{
     path::{Path, PathBuf},
     process::Command,
@@ -11,9 +12,9 @@ use rusqlite::{OpenFlags, fallible_iterator::FallibleIterator, params};
 use tracing::{error, trace, warn};
 
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
     error::GamesParsingError,
-    macros::logs::{debug_fallback_flatpak, debug_path},
+    macros::logs::debug_path,
     parsers::parse_value_json,
     utils::clean_game_title,
 };
@@ -127,22 +128,18 @@ impl ParsedVerdict {
 #[derive(Debug)]
 pub struct Itch {
     path_butler_db: PathBuf,
-    #[allow(dead_code)]
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
 }
 
 impl Itch {
-    pub fn new(path_home: &Path, path_config: &Path) -> Self {
-        let mut path_config_itch = path_config.join("itch");
-        let mut is_using_flatpak = false;
-
-        if !path_config_itch.is_dir() {
-            is_using_flatpak = true;
-            debug_fallback_flatpak!();
-
-            let path_flatpak = path_home.join(".var/app/io.itch.itch");
-            path_config_itch = path_flatpak.join("config/itch");
-        }
+    pub fn new(path_home: &Path, path_config: &Path, install_variant: InstallVariant) -> Self {
+        let path_config_itch = match install_variant {
+            InstallVariant::Flatpak => path_home.join(".var/app/io.itch.itch/config/itch"),
+            InstallVariant::Snap => path_home.join("snap/itch/common/.config/itch"),
+            InstallVariant::Native | InstallVariant::AppImage(_) | InstallVariant::Custom(_) => {
+                path_config.join("itch")
+            }
+        };
 
         let path_butler_db = path_config_itch.join("db").join("butler.db");
 
@@ -150,7 +147,7 @@ impl Itch {
 
         Self {
             path_butler_db,
-            is_using_flatpak,
+            install_variant,
         }
     }
 
@@ -191,6 +188,10 @@ impl Launcher for Itch {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     fn is_detected(&self) -> bool {
         self.path_butler_db.is_file()
     }
@@ -229,6 +230,9 @@ impl Launcher for Itch {
                         path_game_dir: Some(path_game_dir),
                         launch_command,
                         source: LAUNCHER,
+                        // Games are run directly, rather than through the Itch app
+                        install_variant: InstallVariant::Native,
+                        metadata: GameMetadata::default(),
                     }
                 },
             )
@@ -285,20 +289,23 @@ mod test {
         );
     }
 
-    #[test_case(false, ".config"; "standard")]
-    #[test_case(true, "invalid/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, "", ".config"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/path"; "snap")]
     fn test_itch_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_config: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = Itch::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_config),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let mut games = launcher.get_detected_games()?;
         games.sort_by_key(|a| a.title.clone());
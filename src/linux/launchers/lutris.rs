@@ -3,18 +3,19 @@
 // - ~/.config/lutris/
 // - ~/.cache/lutris/
 // - Flatpak: ~/.var/app/net.lutris.Lutris
+// - Snap: ~/snap/lutris/common/
 use std::path::{Path, PathBuf};
 
 use rusqlite::{OpenFlags, fallible_iterator::FallibleIterator, params};
 use tracing::{debug, error, trace, warn};
 
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
     error::GamesParsingError,
-    macros::logs::{debug_fallback_flatpak, debug_path, warn_no_games},
+    macros::logs::{debug_path, warn_no_games},
     utils::{
         clean_game_title, get_existing_image_path, get_launch_command, get_launch_command_flatpak,
-        some_if_dir,
+        get_launch_command_snap, some_if_dir,
     },
 };
 
@@ -64,32 +65,53 @@ pub struct Lutris {
     path_pga_db: PathBuf,
     path_box_art_dir: PathBuf,
     path_icons_dir: PathBuf,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
 }
 
 impl Lutris {
-    pub fn new(path_home: &Path, path_config: &Path, path_cache: &Path, path_data: &Path) -> Self {
-        let path_config_lutris = path_config.join("lutris");
-        let path_cache_lutris = path_cache.join("lutris");
-        let path_data_lutris = path_data.join("lutris");
+    pub fn new(
+        path_home: &Path,
+        path_config: &Path,
+        path_cache: &Path,
+        path_data: &Path,
+        install_variant: InstallVariant,
+    ) -> Self {
+        let (path_config_lutris, path_cache_lutris, path_data_lutris, path_icons_dir) =
+            match install_variant {
+                InstallVariant::Flatpak => {
+                    let path_flatpak = path_home.join(".var/app/net.lutris.Lutris");
+                    (
+                        path_flatpak.join("config/lutris"),
+                        path_flatpak.join("cache/lutris"),
+                        path_flatpak.join("data/lutris"),
+                        path_flatpak.join("data/icons/hicolor/128x128/apps"),
+                    )
+                }
+                InstallVariant::Snap => {
+                    let path_snap = path_home.join("snap/lutris/common");
+                    (
+                        path_snap.join(".config/lutris"),
+                        path_snap.join(".cache/lutris"),
+                        path_snap.join(".local/share/lutris"),
+                        path_snap.join(".local/share/icons/hicolor/128x128/apps"),
+                    )
+                }
+                InstallVariant::Native
+                | InstallVariant::AppImage(_)
+                | InstallVariant::Custom(_) => {
+                    let path_data_lutris = path_data.join("lutris");
+                    let path_icons_dir = path_data_lutris.join("icons/hicolor/128x128/apps");
+                    (
+                        path_config.join("lutris"),
+                        path_cache.join("lutris"),
+                        path_data_lutris,
+                        path_icons_dir,
+                    )
+                }
+            };
 
         let mut path_box_art_dir = path_data_lutris.join("coverart");
-        let mut path_pga_db = path_data_lutris.join("pga.db");
-        let mut path_icons_dir = path_data_lutris.join("icons/hicolor/128x128/apps");
-
-        // Flatpak fallback only if multiple dirs don't exist
-        let mut is_using_flatpak = false;
-        if !path_config_lutris.is_dir()
-            && (!path_cache_lutris.is_dir() || !path_data_lutris.is_dir())
-        {
-            debug_fallback_flatpak!();
-
-            is_using_flatpak = true;
-            let path_flatpak = path_home.join(".var/app/net.lutris.Lutris/data");
-            path_icons_dir = path_flatpak.join("icons/hicolor/128x128/apps");
-            path_box_art_dir = path_flatpak.join("lutris/coverart");
-            path_pga_db = path_flatpak.join("lutris/pga.db")
-        }
+        let path_pga_db = path_data_lutris.join("pga.db");
 
         // Potential fallbacks for cover art dir
         if path_config_lutris.is_dir() && !path_box_art_dir.is_dir() {
@@ -113,7 +135,7 @@ impl Lutris {
             path_box_art_dir,
             path_icons_dir,
             path_pga_db,
-            is_using_flatpak,
+            install_variant,
         }
     }
 
@@ -163,6 +185,10 @@ impl Launcher for Lutris {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let parsed_data = self.get_db_data()?;
@@ -186,10 +212,17 @@ impl Launcher for Lutris {
                         let env_vars = [("LUTRIS_SKIP_INIT", "1")];
                         let game_run_arg = format!("lutris:rungameid/{run_id}");
                         let args = [game_run_arg.as_str()];
-                        if self.is_using_flatpak {
-                            get_launch_command_flatpak("net.lutrsi.Lutris", [], args, env_vars)
-                        } else {
-                            get_launch_command("lutris", args, env_vars)
+                        match &self.install_variant {
+                            InstallVariant::Native => get_launch_command("lutris", args, env_vars),
+                            InstallVariant::Flatpak => {
+                                get_launch_command_flatpak("net.lutris.Lutris", [], args, env_vars)
+                            }
+                            InstallVariant::Snap => {
+                                get_launch_command_snap("lutris", args, env_vars)
+                            }
+                            InstallVariant::AppImage(path) | InstallVariant::Custom(path) => {
+                                get_launch_command(path, args, env_vars)
+                            }
                         }
                     };
 
@@ -234,6 +267,8 @@ impl Launcher for Lutris {
                         path_game_dir,
                         path_icon,
                         source: LAUNCHER.clone(),
+                        install_variant: self.install_variant.clone(),
+                        metadata: GameMetadata::default(),
                     }
                 },
             )
@@ -248,29 +283,33 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".config", ".cache"; "standard")]
-    #[test_case(false, "invalid/path", ".cache"; "fallback")]
-    #[test_case(true, "invalid/path", "invalid/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, "", ".config", ".cache"; "standard")]
+    #[test_case(InstallVariant::Native, "", "invalid/path", ".cache"; "box art fallback")]
+    #[test_case(InstallVariant::Flatpak, "", "invalid/path", "invalid/path"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home", "invalid/path", "invalid/path"; "snap")]
     fn test_lutris_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
+        path_home: &str,
         path_config: &str,
         path_cache: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = Lutris::new(
-            &path_file_system_mock,
+            &path_file_system_mock.join(path_home),
             &path_file_system_mock.join(path_config),
             &path_file_system_mock.join(path_cache),
             &path_file_system_mock.join(".local/share"),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let mut games = launcher.get_detected_games()?;
         games.sort_unstable_by_key(|g| g.title.clone());
 
         assert_eq!(games.len(), 6);
+        assert!(games.iter().all(|g| g.install_variant == install_variant));
 
         assert_eq!(games[0].title, "Battle.net");
         assert_eq!(games[1].title, "Epic Games Store");
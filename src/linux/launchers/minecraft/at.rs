@@ -9,11 +9,14 @@ use nom::IResult;
 use tracing::{error, trace, warn};
 
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
     linux::launchers::minecraft::get_minecraft_title,
-    macros::logs::{debug_fallback_flatpak, debug_path, warn_no_games},
+    macros::logs::{debug_path, warn_no_games},
     parsers::parse_value_json,
-    utils::{get_existing_image_path, get_launch_command, get_launch_command_flatpak, some_if_dir},
+    utils::{
+        get_existing_image_path, get_launch_command, get_launch_command_flatpak,
+        get_launch_command_snap, some_if_dir,
+    },
 };
 
 struct ParsableInstanceConfigData {
@@ -34,20 +37,20 @@ fn parse_instance_config(file_content: &str) -> IResult<&str, ParsableInstanceCo
 #[derive(Debug)]
 pub struct MinecraftAT {
     path_instances: PathBuf,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
 }
 
 impl MinecraftAT {
-    pub fn new(path_home: &Path, path_data: &Path) -> Self {
-        let mut is_using_flatpak = false;
-        let mut path_root = path_data.join("atlauncher");
-
-        if !path_root.is_dir() {
-            debug_fallback_flatpak!();
-
-            is_using_flatpak = true;
-            path_root = path_home.join(".var/app/com.atlauncher.ATLauncher/data");
-        }
+    pub fn new(path_home: &Path, path_data: &Path, install_variant: InstallVariant) -> Self {
+        let path_root = match install_variant {
+            InstallVariant::Flatpak => path_home.join(".var/app/com.atlauncher.ATLauncher/data"),
+            InstallVariant::Snap => {
+                path_home.join("snap/atlauncher/common/.local/share/atlauncher")
+            }
+            InstallVariant::Native | InstallVariant::AppImage(_) | InstallVariant::Custom(_) => {
+                path_data.join("atlauncher")
+            }
+        };
 
         let path_instances = path_root.join("instances");
 
@@ -55,7 +58,7 @@ impl MinecraftAT {
 
         Self {
             path_instances,
-            is_using_flatpak,
+            install_variant,
         }
     }
 }
@@ -69,6 +72,10 @@ impl Launcher for MinecraftAT {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let games: Vec<Game> = read_dir(&self.path_instances)?
@@ -92,10 +99,15 @@ impl Launcher for MinecraftAT {
             .map(|(instance_path, ParsableInstanceConfigData { title })| {
                 let launch_command = {
                     let args = ["--launch", &title];
-                    if self.is_using_flatpak {
-                        get_launch_command_flatpak("com.atlauncher.ATLauncher", [], args, [])
-                    } else {
-                        get_launch_command("atlauncher", args, [])
+                    match &self.install_variant {
+                        InstallVariant::Native => get_launch_command("atlauncher", args, []),
+                        InstallVariant::Flatpak => {
+                            get_launch_command_flatpak("com.atlauncher.ATLauncher", [], args, [])
+                        }
+                        InstallVariant::Snap => get_launch_command_snap("atlauncher", args, []),
+                        InstallVariant::AppImage(path) | InstallVariant::Custom(path) => {
+                            get_launch_command(path, args, [])
+                        }
                     }
                 };
                 trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
@@ -116,6 +128,8 @@ impl Launcher for MinecraftAT {
                     path_game_dir,
                     path_icon,
                     source: LAUNCHER.clone(),
+                    install_variant: self.install_variant.clone(),
+                    metadata: GameMetadata::default(),
                 }
             })
             .collect();
@@ -135,20 +149,21 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".local/share"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, ".local/share"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "invalid/data/path"; "flatpak")]
     fn test_minecraft_at_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
         path_data: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = MinecraftAT::new(
             &path_file_system_mock,
             &path_file_system_mock.join(path_data),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let mut games = launcher.get_detected_games()?;
         games.sort_by_key(|a| a.title.clone());
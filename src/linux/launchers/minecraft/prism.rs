@@ -9,11 +9,14 @@ use nom::IResult;
 use tracing::{error, trace, warn};
 
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
     linux::launchers::minecraft::get_minecraft_title,
-    macros::logs::{debug_fallback_flatpak, debug_path, warn_no_games},
+    macros::logs::{debug_path, warn_no_games},
     parsers::{parse_until_key_cfg, parse_value_cfg},
-    utils::{get_launch_command, get_launch_command_flatpak, some_if_dir, some_if_file},
+    utils::{
+        get_launch_command, get_launch_command_flatpak, get_launch_command_snap, some_if_dir,
+        some_if_file,
+    },
 };
 
 const LAUNCHER: SupportedLaunchers = SupportedLaunchers::MinecraftPrism;
@@ -30,21 +33,22 @@ struct ParsableInstanceData {
 pub struct MinecraftPrism {
     path_root: PathBuf,
     path_config: PathBuf,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
 }
 
 impl MinecraftPrism {
-    pub fn new(path_home: &Path, path_data: &Path) -> Self {
-        let mut is_using_flatpak = false;
-        let mut path_root = path_data.join("PrismLauncher");
-
-        if !path_root.is_dir() {
-            debug_fallback_flatpak!();
-
-            is_using_flatpak = true;
-            path_root =
-                path_home.join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher");
-        }
+    pub fn new(path_home: &Path, path_data: &Path, install_variant: InstallVariant) -> Self {
+        let path_root = match install_variant {
+            InstallVariant::Flatpak => {
+                path_home.join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher")
+            }
+            InstallVariant::Snap => {
+                path_home.join("snap/prismlauncher/common/.local/share/PrismLauncher")
+            }
+            InstallVariant::Native | InstallVariant::AppImage(_) | InstallVariant::Custom(_) => {
+                path_data.join("PrismLauncher")
+            }
+        };
 
         let path_config = path_root.join("prismlauncher.cfg");
 
@@ -53,7 +57,7 @@ impl MinecraftPrism {
         Self {
             path_root,
             path_config,
-            is_using_flatpak,
+            install_variant,
         }
     }
 
@@ -91,6 +95,10 @@ impl Launcher for MinecraftPrism {
         SupportedLaunchers::MinecraftPrism
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     #[tracing::instrument(level = "trace")]
     fn get_detected_games(&self) -> GamesResult {
         let file_content = read_to_string(&self.path_config)?;
@@ -135,10 +143,18 @@ impl Launcher for MinecraftPrism {
 
                 let launch_command = {
                     let args = ["--launch", &name];
-                    if self.is_using_flatpak {
-                        get_launch_command_flatpak("org.prismlauncher.PrismLauncher", [], args, [])
-                    } else {
-                        get_launch_command("prismlauncher", args, [])
+                    match &self.install_variant {
+                        InstallVariant::Native => get_launch_command("prismlauncher", args, []),
+                        InstallVariant::Flatpak => get_launch_command_flatpak(
+                            "org.prismlauncher.PrismLauncher",
+                            [],
+                            args,
+                            [],
+                        ),
+                        InstallVariant::Snap => get_launch_command_snap("prismlauncher", args, []),
+                        InstallVariant::AppImage(path) | InstallVariant::Custom(path) => {
+                            get_launch_command(path, args, [])
+                        }
                     }
                 };
                 trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
@@ -158,6 +174,8 @@ impl Launcher for MinecraftPrism {
                     path_game_dir,
                     path_icon,
                     source: LAUNCHER.clone(),
+                    install_variant: self.install_variant.clone(),
+                    metadata: GameMetadata::default(),
                 }
             })
             .collect();
@@ -185,20 +203,21 @@ mod tests {
     use super::*;
     use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
 
-    #[test_case(false, ".local/share"; "standard")]
-    #[test_case(true, "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, ".local/share"; "standard")]
+    #[test_case(InstallVariant::Flatpak, "invalid/data/path"; "flatpak")]
     fn test_minecraft_prism_launcher(
-        is_testing_flatpak: bool,
+        install_variant: InstallVariant,
         path_data: &str,
     ) -> Result<(), GamesParsingError> {
         let path_file_system_mock = get_mock_file_system_path();
         let launcher = MinecraftPrism::new(
             &path_file_system_mock,
             &path_file_system_mock.join(path_data),
+            install_variant.clone(),
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let mut games = launcher.get_detected_games()?;
         games.sort_by_key(|a| a.title.clone());
@@ -1,6 +1,9 @@
 pub mod bottles;
+pub mod desktop_entries;
+pub mod flatpak;
 pub mod heroic;
 pub mod itch;
 pub mod lutris;
 pub mod minecraft;
+pub mod snap;
 pub mod steam;
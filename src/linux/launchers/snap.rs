@@ -0,0 +1,181 @@
+// PATHS:
+// - /snap/<name>/current/meta/gui/
+use std::{
+    fs::{read_dir, read_to_string},
+    path::{Path, PathBuf},
+    slice,
+};
+
+use tracing::{debug, trace, warn};
+
+use super::desktop_entries::{find_desktop_files, find_icon, parse_desktop_entry};
+use crate::{
+    data::{Game, GameMetadata, GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    macros::logs::{debug_path, warn_no_games},
+    utils::{clean_game_title, get_launch_command_snap, some_if_dir, some_if_file},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Snap;
+
+/// Names of snaps which are launchers already handled by other sources
+const SUPPORTED_LAUNCHER_NAMES: [&str; 7] = [
+    "steam",
+    "heroic",
+    "lutris",
+    "bottles",
+    "itch",
+    "prismlauncher",
+    "atlauncher",
+];
+
+// SNAP LAUNCHER ------------------------------------------------------------------------
+#[derive(Debug)]
+pub struct Snap {
+    path_snaps: PathBuf,
+}
+
+impl Snap {
+    pub fn new(path_snaps: &Path) -> Self {
+        let path_snaps = path_snaps.to_path_buf();
+
+        debug_path!("snaps directory", path_snaps);
+
+        Self { path_snaps }
+    }
+
+    /// Get all games from the desktop entries shipped by the given snap
+    #[tracing::instrument(level = "trace")]
+    fn get_games(&self, snap_name: &str, path_current: &Path) -> Vec<Game> {
+        let path_gui = path_current.join("meta/gui");
+        let path_current_str = path_current.to_string_lossy();
+
+        find_desktop_files(slice::from_ref(&path_gui))
+            .into_iter()
+            .filter_map(|path_desktop_file| {
+                let file_content = read_to_string(&path_desktop_file)
+                    .map_err(|e| warn!("{LAUNCHER} - failed to read {path_desktop_file:?}: {e}"))
+                    .ok()?;
+                let entry = parse_desktop_entry(&file_content)?;
+
+                if !entry.is_visible_game() {
+                    trace!(
+                        "{LAUNCHER} - Skipped entry which is not a visible game: {path_desktop_file:?}"
+                    );
+                    return None;
+                }
+
+                // `Exec` should start with the snap app to run, e.g. `name` or `name.app`
+                let exec_args = entry.exec_args();
+                let app = exec_args
+                    .first()
+                    .filter(|a| *a == snap_name || a.starts_with(&format!("{snap_name}.")))
+                    .map_or(snap_name, String::as_str);
+                let args = exec_args.iter().skip(1).map(String::as_str);
+
+                let launch_command = get_launch_command_snap(app, args, []);
+
+                let title = clean_game_title(&entry.name);
+                trace!("{LAUNCHER} - launch command for '{title}': {launch_command:?}");
+
+                let path_icon = entry
+                    .icon
+                    .as_deref()
+                    .and_then(|i| find_icon(&i.replace("${SNAP}", &path_current_str), &[]))
+                    .or_else(|| some_if_file(path_gui.join("icon.png")))
+                    .or_else(|| some_if_file(path_gui.join("icon.svg")));
+                let path_game_dir = some_if_dir(path_current.to_path_buf());
+
+                trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
+                trace!("{LAUNCHER} - Icon for '{title}': {path_icon:?}");
+
+                Some(Game {
+                    title,
+                    launch_command,
+                    path_box_art: None,
+                    path_game_dir,
+                    path_icon,
+                    source: LAUNCHER.clone(),
+                    install_variant: InstallVariant::Snap,
+                    metadata: GameMetadata::default(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Launcher for Snap {
+    fn get_launcher_type(&self) -> SupportedLaunchers {
+        LAUNCHER
+    }
+
+    fn get_install_variant(&self) -> InstallVariant {
+        InstallVariant::Snap
+    }
+
+    fn is_detected(&self) -> bool {
+        self.path_snaps.is_dir()
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn get_detected_games(&self) -> GamesResult {
+        let games: Vec<Game> = read_dir(&self.path_snaps)
+            .inspect_err(|e| debug!("{LAUNCHER} - could not read {:?}: {e}", self.path_snaps))?
+            .flatten()
+            .filter_map(|dir_entry| {
+                let snap_name = dir_entry.file_name().to_str()?.to_owned();
+                if SUPPORTED_LAUNCHER_NAMES.contains(&snap_name.as_str()) {
+                    return None;
+                }
+
+                let path_current = some_if_dir(dir_entry.path().join("current"))?;
+                Some(self.get_games(&snap_name, &path_current))
+            })
+            .flatten()
+            .collect();
+
+        if games.is_empty() {
+            warn_no_games!();
+        }
+
+        Ok(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
+
+    #[test]
+    fn test_snap_launcher() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = Snap::new(&path_file_system_mock.join("snap"));
+
+        assert!(launcher.is_detected());
+
+        let mut games = launcher.get_detected_games()?;
+        games.sort_by_key(|g| g.title.clone());
+
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].title, "Mindustry");
+        assert_eq!(games[1].title, "SuperTuxKart");
+
+        assert_eq!(
+            games[0].launch_command.get_args().collect::<Vec<_>>(),
+            ["run", "mindustry.server", "--headless"]
+        );
+        assert_eq!(
+            games[1].launch_command.get_args().collect::<Vec<_>>(),
+            ["run", "supertuxkart"]
+        );
+
+        assert!(games[0].path_icon.is_none());
+        assert!(games[1].path_icon.is_some());
+
+        assert!(games.iter().all(|g| g.path_game_dir.is_some()));
+        assert!(games.iter().all(|g| g.path_box_art.is_none()));
+
+        Ok(())
+    }
+}
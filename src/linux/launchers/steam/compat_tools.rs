@@ -0,0 +1,307 @@
+// PATHS:
+// - <steam dir>/config/config.vdf (`CompatToolMapping`)
+// - <steam dir>/compatibilitytools.d/*/compatibilitytool.vdf
+// - <library>/steamapps/common/Proton*
+// - <library>/steamapps/compatdata/<app id>
+use std::{
+    collections::HashMap,
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+use tracing::{debug, error, trace};
+
+use super::get_steamapps_dir;
+use crate::{
+    data::{CompatTool, SupportedLaunchers},
+    macros::logs::debug_path,
+    utils::some_if_dir,
+    vdf,
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Steam;
+/// Key of the `CompatToolMapping` entry giving the default tool for all Windows games
+const DEFAULT_MAPPING_APP_ID: u32 = 0;
+
+/// Compatibility tools installed for a Steam directory, and the tools selected for each app
+#[derive(Debug, Default)]
+pub struct CompatTools {
+    /// Names of the tools selected for specific apps, including the default for all apps. Empty
+    /// names are kept, as they mean the app is forced to run natively.
+    mappings: HashMap<u32, String>,
+    /// Install directories of all installed tools, by name
+    paths_tools: HashMap<String, PathBuf>,
+}
+
+impl CompatTools {
+    /// Find the compatibility tools of the given Steam directory, searching the given libraries
+    /// for Valve's own Proton versions
+    #[tracing::instrument(level = "trace")]
+    pub fn new(path_steam_dir: &Path, paths_libraries: &[PathBuf]) -> Self {
+        let mut paths_tools = get_custom_tools(path_steam_dir);
+        for path_library in paths_libraries {
+            for (name, path) in get_official_tools(path_library) {
+                paths_tools.entry(name).or_insert(path);
+            }
+        }
+
+        let compat_tools = Self {
+            mappings: get_mappings(path_steam_dir),
+            paths_tools,
+        };
+        trace!("{LAUNCHER} - Compatibility tools: {compat_tools:?}");
+
+        compat_tools
+    }
+
+    /// Get the compatibility tool used for the given app, installed in the given library.
+    ///
+    /// A tool selected for the app specifically always takes priority. Otherwise, the default tool
+    /// is used if the app doesn't support Linux. If that isn't known, the default tool is assumed
+    /// to be used if the app has a prefix.
+    pub fn get_compat_tool(
+        &self,
+        app_id: u32,
+        path_library: &Path,
+        supports_linux: Option<bool>,
+    ) -> Option<CompatTool> {
+        let path_prefix = some_if_dir(
+            get_steamapps_dir(path_library)
+                .join("compatdata")
+                .join(app_id.to_string()),
+        );
+
+        let name = match self.mappings.get(&app_id) {
+            // An empty name means the app is forced to run natively
+            Some(name) if name.is_empty() => return None,
+            Some(name) => name,
+            None if supports_linux.map_or(path_prefix.is_some(), |l| !l) => {
+                self.mappings.get(&DEFAULT_MAPPING_APP_ID)?
+            }
+            None => return None,
+        };
+
+        Some(CompatTool {
+            name: name.clone(),
+            path_tool: self.paths_tools.get(name).cloned(),
+            path_prefix,
+        })
+    }
+
+    /// Get the compatibility tool selected for the given non-Steam game. Unlike Steam games, the
+    /// default tool is never used for these.
+    pub fn get_compat_tool_shortcut(
+        &self,
+        app_id: u32,
+        path_steam_dir: &Path,
+    ) -> Option<CompatTool> {
+        self.mappings
+            .contains_key(&app_id)
+            .then(|| self.get_compat_tool(app_id, path_steam_dir, None))
+            .flatten()
+    }
+}
+
+/// Get the names of the tools selected for each app from `config.vdf`
+#[tracing::instrument(level = "trace")]
+fn get_mappings(path_steam_dir: &Path) -> HashMap<u32, String> {
+    let path_config = path_steam_dir.join("config/config.vdf");
+
+    debug_path!("config.vdf", path_config);
+
+    if !path_config.is_file() {
+        debug!("{LAUNCHER} - config.vdf not found, so no compatibility tools are selected");
+        return HashMap::new();
+    }
+
+    let Ok(config) = vdf::parse_file(&path_config)
+        .inspect_err(|e| error!("{LAUNCHER} - Error with parsing config.vdf:\n{e}"))
+    else {
+        return HashMap::new();
+    };
+
+    config
+        .get_object_at(&[
+            "InstallConfigStore",
+            "Software",
+            "Valve",
+            "Steam",
+            "CompatToolMapping",
+        ])
+        .into_iter()
+        .flat_map(vdf::KeyValues::iter)
+        .filter_map(|(app_id, mapping)| {
+            let name = mapping.as_object()?.get_str("name")?;
+            Some((app_id.parse().ok()?, name.to_owned()))
+        })
+        .collect()
+}
+
+/// Get custom tools, such as GE-Proton, installed under `compatibilitytools.d`
+#[tracing::instrument(level = "trace")]
+fn get_custom_tools(path_steam_dir: &Path) -> HashMap<String, PathBuf> {
+    let Ok(entries) = read_dir(path_steam_dir.join("compatibilitytools.d")) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter_map(|path_tool_dir| {
+            let manifest = vdf::parse_file(&path_tool_dir.join("compatibilitytool.vdf"))
+                .inspect_err(|e| {
+                    debug!("{LAUNCHER} - Skipped compatibility tool at {path_tool_dir:?}: {e}")
+                })
+                .ok()?;
+
+            let tools = manifest
+                .get_object_at(&["compatibilitytools", "compat_tools"])?
+                .iter()
+                .filter_map(|(name, tool)| {
+                    let path_tool = match tool.as_object()?.get_str("install_path") {
+                        Some(".") | None => path_tool_dir.clone(),
+                        Some(install_path) => path_tool_dir.join(install_path),
+                    };
+                    Some((name.to_owned(), path_tool))
+                })
+                .collect::<Vec<_>>();
+
+            Some(tools)
+        })
+        .flatten()
+        .collect()
+}
+
+/// Get Valve's own Proton versions, which are installed as apps in `steamapps/common`
+#[tracing::instrument(level = "trace")]
+fn get_official_tools(path_library: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = read_dir(get_steamapps_dir(path_library).join("common")) else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let name = get_official_tool_name(e.file_name().to_str()?)?;
+            Some((name, e.path()))
+        })
+        .collect()
+}
+
+/// Get the internal name of an official Proton version from the name of its install directory,
+/// e.g. `Proton 9.0` -> `proton_9`, `Proton 5.13` -> `proton_513`, `Proton - Experimental` ->
+/// `proton_experimental`
+fn get_official_tool_name(dir_name: &str) -> Option<String> {
+    let rest = dir_name
+        .strip_prefix("Proton")?
+        .trim_start_matches([' ', '-'])
+        .trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    let version = rest.split_whitespace().next()?;
+    if let Some((major, minor)) = version
+        .split_once('.')
+        .filter(|(major, _)| major.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Some(match minor {
+            "0" => format!("proton_{major}"),
+            minor => format!("proton_{major}{minor}"),
+        });
+    }
+
+    Some(format!(
+        "proton_{}",
+        rest.to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("_")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test_case("Proton 9.0", Some("proton_9"))]
+    #[test_case("Proton 9.0 (Beta)", Some("proton_9"))]
+    #[test_case("Proton 6.3", Some("proton_63"))]
+    #[test_case("Proton 5.13", Some("proton_513"))]
+    #[test_case("Proton - Experimental", Some("proton_experimental"))]
+    #[test_case("Proton Hotfix", Some("proton_hotfix"))]
+    #[test_case("Proton", None)]
+    #[test_case("Terraria", None)]
+    fn test_get_official_tool_name(dir_name: &str, expected: Option<&str>) {
+        assert_eq!(get_official_tool_name(dir_name).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_compat_tools() {
+        let path_file_system_mock = get_mock_file_system_path();
+        let path_steam_dir = path_file_system_mock.join(".local/share/Steam");
+        let path_library = path_file_system_mock.join("steam_libraries/2");
+
+        let compat_tools = CompatTools::new(&path_steam_dir, std::slice::from_ref(&path_library));
+
+        // Default tool, used as the game only supports Windows
+        assert_eq!(
+            compat_tools.get_compat_tool(2767030, &path_library, Some(false)),
+            Some(CompatTool {
+                name: String::from("proton_experimental"),
+                path_tool: Some(path_library.join("steamapps/common/Proton - Experimental")),
+                path_prefix: Some(path_library.join("steamapps/compatdata/2767030")),
+            })
+        );
+
+        // Tool selected for the game specifically
+        assert_eq!(
+            compat_tools.get_compat_tool(1062090, &path_library, Some(true)),
+            Some(CompatTool {
+                name: String::from("GE-Proton9-20"),
+                path_tool: Some(path_steam_dir.join("compatibilitytools.d/GE-Proton9-20")),
+                path_prefix: Some(path_library.join("steamapps/compatdata/1062090")),
+            })
+        );
+
+        // Forced to run natively, or supports Linux
+        assert!(
+            compat_tools
+                .get_compat_tool(105600, &path_library, Some(false))
+                .is_none()
+        );
+        assert!(
+            compat_tools
+                .get_compat_tool(620, &path_library, Some(true))
+                .is_none()
+        );
+
+        // Supported OS unknown, so the default is only used if a prefix exists
+        assert!(
+            compat_tools
+                .get_compat_tool(2767030, &path_library, None)
+                .is_some()
+        );
+        assert!(
+            compat_tools
+                .get_compat_tool(620, &path_library, None)
+                .is_none()
+        );
+
+        // Non-Steam games only use tools selected for them specifically
+        assert!(
+            compat_tools
+                .get_compat_tool_shortcut(2784013951, &path_steam_dir)
+                .is_some_and(|t| t.path_prefix.is_some())
+        );
+        assert!(
+            compat_tools
+                .get_compat_tool_shortcut(3271672210, &path_steam_dir)
+                .is_none()
+        );
+    }
+}
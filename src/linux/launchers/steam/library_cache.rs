@@ -0,0 +1,281 @@
+// PATHS:
+// - <steam dir>/appcache/librarycache/<app id>_library_600x900.jpg
+// - <steam dir>/appcache/librarycache/<app id>_icon.jpg
+// - <steam dir>/appcache/librarycache/<app id>/<hash>.jpg
+// - <steam dir>/appcache/librarycache/<app id>/<hash>/library_600x900.jpg
+use std::{
+    collections::HashMap,
+    fs::{FileType, read_dir},
+    path::{Path, PathBuf},
+};
+
+use tracing::trace;
+
+use crate::{data::SupportedLaunchers, macros::logs::debug_path};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Steam;
+
+const BOX_ART_NAME: &str = "library_600x900";
+const BOX_ART_NAME_CAPSULE: &str = "library_capsule";
+
+/// Box art and icon of an app in Steam's `librarycache`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppImages {
+    pub path_box_art: Option<PathBuf>,
+    pub path_icon: Option<PathBuf>,
+}
+
+/// Index of the images in Steam's `librarycache`, built once per Steam directory so that it
+/// doesn't need to be searched separately for each app
+#[derive(Debug, Default)]
+pub struct LibraryCache {
+    images: HashMap<u32, AppImages>,
+}
+
+impl LibraryCache {
+    /// Index the images of all apps matching the filter in the `librarycache` of the given Steam
+    /// directory
+    #[tracing::instrument(level = "trace", skip(filter))]
+    pub fn new(path_steam_dir: &Path, filter: impl Fn(u32) -> bool) -> Self {
+        let path_library_cache = path_steam_dir.join("appcache/librarycache");
+
+        debug_path!("library cache", path_library_cache);
+
+        let Ok(entries) = read_dir(&path_library_cache) else {
+            return Self::default();
+        };
+
+        let mut images = HashMap::<u32, AppImages>::new();
+        let mut images_old = HashMap::<u32, AppImages>::new();
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            // New structure, with a directory for each app
+            if file_type.is_dir() {
+                if let Some(app_id) = file_name.parse().ok().filter(|id| filter(*id)) {
+                    images.insert(app_id, get_app_dir_images(&entry.path()));
+                }
+                continue;
+            }
+
+            // Old structure, with the images of all apps in one directory,
+            // e.g. `105600_library_600x900.jpg`
+            let Some((app_id, name)) = file_name.split_once('_') else {
+                continue;
+            };
+            let Some(app_id) = app_id.parse().ok().filter(|id| filter(*id)) else {
+                continue;
+            };
+            let app_images = images_old.entry(app_id).or_default();
+            match name {
+                "library_600x900.jpg" => app_images.path_box_art = Some(entry.path()),
+                "icon.jpg" => app_images.path_icon = Some(entry.path()),
+                _ => {}
+            }
+        }
+
+        // Images in the new structure take priority
+        for (app_id, old) in images_old {
+            let app_images = images.entry(app_id).or_default();
+            app_images.path_box_art = app_images.path_box_art.take().or(old.path_box_art);
+            app_images.path_icon = app_images.path_icon.take().or(old.path_icon);
+        }
+
+        trace!(
+            "{LAUNCHER} - Indexed library cache images of {} apps",
+            images.len()
+        );
+
+        Self { images }
+    }
+
+    /// Get the images of the app with the given ID
+    #[must_use]
+    pub fn get_images(&self, app_id: u32) -> AppImages {
+        self.images.get(&app_id).cloned().unwrap_or_default()
+    }
+}
+
+/// Get the images in an app's directory. The icon is named after its SHA-1 hash, e.g.
+/// `a4c7a8cce43d797c275aaf601d6855b90ba87769.jpg`, and box art can also be in a sub-directory,
+/// which is only searched if there is none directly in the app's directory.
+fn get_app_dir_images(path_app_dir: &Path) -> AppImages {
+    let mut box_art = BoxArt::default();
+    let mut path_icon = None;
+    let mut sub_dirs = Vec::new();
+
+    for (path, file_type, file_name) in read_dir_entries(path_app_dir) {
+        if file_type.is_dir() {
+            sub_dirs.push(path);
+        } else if let Some(rank) = get_box_art_rank(&file_name) {
+            box_art.add(rank, path);
+        } else if is_icon_file_name(&file_name) && path_icon.as_ref().is_none_or(|p| path < *p) {
+            path_icon = Some(path);
+        }
+    }
+
+    if !box_art.is_exact_match() {
+        for (path, file_type, file_name) in sub_dirs.iter().flat_map(|p| read_dir_entries(p)) {
+            if let Some(rank) = get_box_art_rank(&file_name).filter(|_| file_type.is_file()) {
+                box_art.add(rank, path);
+            }
+        }
+    }
+
+    AppImages {
+        path_box_art: box_art.best.map(|(_, path)| path),
+        path_icon,
+    }
+}
+
+/// Get the path, file type and file name of each entry in a directory
+fn read_dir_entries(path_dir: &Path) -> impl Iterator<Item = (PathBuf, FileType, String)> {
+    read_dir(path_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            Some((
+                entry.path(),
+                entry.file_type().ok()?,
+                entry.file_name().into_string().ok()?,
+            ))
+        })
+}
+
+/// The best box art found for an app, by the rank given by [`get_box_art_rank`]. Ties are broken by
+/// path so that the choice doesn't depend on the order the files are read in.
+#[derive(Debug, Default)]
+struct BoxArt {
+    best: Option<(u8, PathBuf)>,
+}
+
+impl BoxArt {
+    fn add(&mut self, rank: u8, path: PathBuf) {
+        if self
+            .best
+            .as_ref()
+            .is_none_or(|best| (rank, &path) < (best.0, &best.1))
+        {
+            self.best = Some((rank, path));
+        }
+    }
+
+    fn is_exact_match(&self) -> bool {
+        self.best.as_ref().is_some_and(|(rank, _)| *rank == 0)
+    }
+}
+
+/// Returns how well the file name matches that of box art, with lower being better, or [`None`]
+/// if it isn't box art. It may be named differently depending on the language, e.g.
+/// `library_600x900_russian.jpg`, so it isn't matched exactly.
+fn get_box_art_rank(file_name: &str) -> Option<u8> {
+    if file_name.strip_suffix(".jpg") == Some(BOX_ART_NAME) {
+        Some(0)
+    } else if file_name.contains(BOX_ART_NAME) {
+        Some(1)
+    } else if file_name.contains(BOX_ART_NAME_CAPSULE) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if the file name is a hex encoded SHA-1 hash with a `.jpg` extension
+fn is_icon_file_name(file_name: &str) -> bool {
+    file_name
+        .strip_suffix(".jpg")
+        .is_some_and(|hash| hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test]
+    fn test_library_cache() {
+        let path_steam_dir = get_mock_file_system_path().join(".local/share/Steam");
+        let path_library_cache = path_steam_dir.join("appcache/librarycache");
+        let library_cache = LibraryCache::new(&path_steam_dir, |_| true);
+
+        // Old structure
+        assert_eq!(
+            library_cache.get_images(105600),
+            AppImages {
+                path_box_art: Some(path_library_cache.join("105600_library_600x900.jpg")),
+                path_icon: None,
+            }
+        );
+
+        // New structure
+        assert_eq!(
+            library_cache.get_images(2078450),
+            AppImages {
+                path_box_art: Some(
+                    path_library_cache.join(
+                        "2078450/96d54932350d6a38429b6c79cb4966fbd3b00acf/library_600x900.jpg"
+                    )
+                ),
+                path_icon: Some(
+                    path_library_cache.join("2078450/795e85364189511f4990861b578084deef086cb1.jpg")
+                ),
+            }
+        );
+        // Sub-directories aren't searched, as the app's directory has box art
+        assert_eq!(
+            library_cache.get_images(8930),
+            AppImages {
+                path_box_art: Some(path_library_cache.join("8930/library_600x900.jpg")),
+                path_icon: Some(
+                    path_library_cache.join("8930/a4c7a8cce43d797c275aaf601d6855b90ba87769.jpg")
+                ),
+            }
+        );
+        assert!(
+            library_cache
+                .get_images(2073850)
+                .path_box_art
+                .is_some_and(|p| p.ends_with("library_600x900_russian.jpg"))
+        );
+        assert!(
+            library_cache
+                .get_images(2767030)
+                .path_box_art
+                .is_some_and(|p| p.ends_with("library_capsule.jpg"))
+        );
+
+        assert_eq!(library_cache.get_images(620), AppImages::default());
+
+        // Only apps matching the filter are indexed
+        let library_cache = LibraryCache::new(&path_steam_dir, |app_id| app_id == 8930);
+        assert!(library_cache.get_images(8930).path_box_art.is_some());
+        assert_eq!(library_cache.get_images(105600), AppImages::default());
+        assert_eq!(library_cache.get_images(2078450), AppImages::default());
+    }
+
+    #[test_case("library_600x900.jpg", Some(0))]
+    #[test_case("library_600x900_russian.jpg", Some(1))]
+    #[test_case("library_capsule.jpg", Some(2))]
+    #[test_case("library_hero.jpg", None)]
+    fn test_get_box_art_rank(file_name: &str, expected: Option<u8>) {
+        assert_eq!(get_box_art_rank(file_name), expected);
+    }
+
+    #[test_case("a4c7a8cce43d797c275aaf601d6855b90ba87769.jpg", true)]
+    #[test_case("a4c7a8cce43d797c275aaf601d6855b90ba87769.png", false)]
+    #[test_case("library_600x900_schinesexxxxxxxxxxxxxxxx.jpg", false; "same length")]
+    #[test_case("header.jpg", false)]
+    fn test_is_icon_file_name(file_name: &str, expected: bool) {
+        assert_eq!(is_icon_file_name(file_name), expected);
+    }
+}
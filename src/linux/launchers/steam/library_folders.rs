@@ -0,0 +1,221 @@
+use std::{
+    collections::BTreeMap,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
+
+use rustix::fs::statvfs;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use tracing::{trace, warn};
+
+use super::get_steamapps_dir;
+use crate::{
+    data::SupportedLaunchers,
+    error::GamesParsingError,
+    macros::logs::debug_path,
+    vdf::{self, KeyValues, Value},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Steam;
+
+/// A Steam library folder, as listed in `steamapps/libraryfolders.vdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LibraryFolder {
+    /// Path to the library folder, which contains its `steamapps` directory.
+    pub path: PathBuf,
+    /// Label given to the library by the user, which is empty if none was given.
+    pub label: String,
+    /// ID Steam uses to recognise the library, even if it is moved.
+    pub content_id: Option<u64>,
+    /// Total size of the drive containing the library in bytes, as recorded by Steam. This is
+    /// `0` for the library within the main Steam directory.
+    pub total_size: Option<u64>,
+    /// IDs of the apps installed in this library, mapped to their size on disk in bytes.
+    pub apps: BTreeMap<u32, u64>,
+    /// Mount point of the file system containing the library, if it exists.
+    pub mount_point: Option<PathBuf>,
+    /// Space available on the file system containing the library in bytes, if it exists.
+    pub free_space: Option<u64>,
+    /// Whether the library's directory exists. A library which doesn't exist may be on a drive
+    /// which isn't mounted, or may have been deleted without being removed from Steam, as these
+    /// can't be told apart.
+    pub exists: bool,
+}
+
+impl LibraryFolder {
+    /// Create a [`LibraryFolder`] from an entry of `libraryfolders.vdf`
+    fn new(path: PathBuf, library: Option<&KeyValues>) -> Self {
+        let get_str = |key: &str| library.and_then(|l| l.get_str(key));
+        let get_parsed = |key: &str| library.and_then(|l| l.get_parsed::<u64>(key));
+
+        let apps = library
+            .and_then(|l| l.get_object("apps"))
+            .into_iter()
+            .flat_map(KeyValues::iter)
+            .filter_map(|(app_id, size)| Some((app_id.parse().ok()?, size.parse().unwrap_or(0))))
+            .collect();
+
+        let exists = path.is_dir();
+        let (mount_point, free_space) = if exists {
+            (get_mount_point(&path), get_free_space(&path))
+        } else {
+            warn!(
+                "{LAUNCHER} - Library does not exist, its drive may not be mounted or it may have been deleted: {path:?}"
+            );
+            (None, None)
+        };
+
+        LibraryFolder {
+            label: get_str("label").unwrap_or_default().to_owned(),
+            content_id: get_parsed("contentid"),
+            total_size: get_parsed("totalsize"),
+            apps,
+            mount_point,
+            free_space,
+            exists,
+            path,
+        }
+    }
+}
+
+/// Find the mount point of the file system containing the given path, by walking up its
+/// ancestors until the device changes
+#[tracing::instrument(level = "trace")]
+fn get_mount_point(path: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+    let device = path.metadata().ok()?.dev();
+
+    path.ancestors()
+        .take_while(|p| p.metadata().is_ok_and(|m| m.dev() == device))
+        .last()
+        .map(Path::to_path_buf)
+}
+
+/// Get the space available to unprivileged users on the file system containing the given path
+#[tracing::instrument(level = "trace")]
+fn get_free_space(path: &Path) -> Option<u64> {
+    statvfs(path)
+        .inspect_err(|e| warn!("{LAUNCHER} - Could not get free space for {path:?}: {e}"))
+        .ok()
+        .map(|stats| stats.f_bavail.saturating_mul(stats.f_frsize))
+}
+
+/// Get all library folders listed in the given parsed `libraryfolders.vdf`
+fn parse_library_folders(libraries_vdf: &KeyValues) -> Vec<LibraryFolder> {
+    let Some(library_folders) = libraries_vdf.get_object("libraryfolders") else {
+        warn!("{LAUNCHER} - No `libraryfolders` block found");
+        return vec![];
+    };
+
+    library_folders
+        .iter()
+        // Libraries are keyed by their index, other keys hold unrelated data
+        .filter(|(key, _)| key.parse::<u32>().is_ok())
+        .filter_map(|(_, library)| {
+            // Older versions of the file give the path of each library directly
+            let (path_library, library) = match library {
+                Value::Object(library) => (library.get_str("path")?, Some(library)),
+                Value::String(path_library) | Value::Typed(path_library, _) => {
+                    (path_library.as_str(), None)
+                }
+            };
+
+            let library_folder = LibraryFolder::new(PathBuf::from(path_library), library);
+            trace!("{LAUNCHER} - Library folder: {library_folder:?}");
+
+            Some(library_folder)
+        })
+        .collect()
+}
+
+/// Get all library folders of the Steam directory at the given path
+#[tracing::instrument(level = "trace")]
+pub fn get_library_folders(path_steam_dir: &Path) -> Result<Vec<LibraryFolder>, GamesParsingError> {
+    let libraries_vdf_path = get_steamapps_dir(path_steam_dir).join("libraryfolders.vdf");
+
+    debug_path!("libraryfolders.vdf", libraries_vdf_path);
+
+    Ok(parse_library_folders(&vdf::parse_file(
+        &libraries_vdf_path,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test]
+    fn test_get_library_folders() -> Result<(), GamesParsingError> {
+        let path_steam_dir = get_mock_file_system_path().join(".local/share/Steam");
+        let library_folders = get_library_folders(&path_steam_dir)?;
+
+        // Neither library in the mock exists
+        assert_eq!(
+            library_folders,
+            [
+                LibraryFolder {
+                    path: PathBuf::from("/not/a/valid/library"),
+                    label: String::new(),
+                    content_id: Some(2698625989248208323),
+                    total_size: Some(0),
+                    apps: BTreeMap::from([(228980, 399788250)]),
+                    mount_point: None,
+                    free_space: None,
+                    exists: false,
+                },
+                LibraryFolder {
+                    path: PathBuf::from("/not_a_valid/library"),
+                    label: String::new(),
+                    content_id: Some(7103446667635577235),
+                    total_size: Some(1967855026176),
+                    apps: BTreeMap::from([(2078450, 16414937689), (2348590, 1222652790)]),
+                    mount_point: None,
+                    free_space: None,
+                    exists: false,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_library_folders() -> Result<(), GamesParsingError> {
+        let path_library = get_mock_file_system_path().join("steam_libraries/1");
+        let libraries_vdf = vdf::parse(&format!(
+            r#""libraryfolders"
+            {{
+                "contentstatsid" "123"
+                "0" {{ "path" "{}" "label" "Games" }}
+                "1" "/old/format/library"
+            }}"#,
+            path_library.display()
+        ))?;
+
+        let library_folders = parse_library_folders(&libraries_vdf);
+        assert_eq!(library_folders.len(), 2);
+
+        let library = &library_folders[0];
+        assert_eq!(library.path, path_library);
+        assert_eq!(library.label, "Games");
+        assert!(library.content_id.is_none());
+        assert!(library.apps.is_empty());
+        assert!(library.exists);
+        assert!(
+            library
+                .mount_point
+                .as_ref()
+                .is_some_and(|p| path_library.canonicalize().unwrap().starts_with(p))
+        );
+        assert!(library.free_space.is_some());
+
+        let library = &library_folders[1];
+        assert_eq!(library.path, PathBuf::from("/old/format/library"));
+        assert!(!library.exists);
+
+        Ok(())
+    }
+}
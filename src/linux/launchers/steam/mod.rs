@@ -1,53 +1,461 @@
+//! Steam-specific data which isn't covered by [`Game`](crate::data::Game), such as library
+//! folders and Workshop items, and support for adding games to Steam as shortcuts.
+//!
+//! ```rust,no_run
+//! use lib_game_detector::steam::get_steam_dirs;
+//!
+//! for steam_dir in get_steam_dirs() {
+//!     for library in steam_dir.get_library_folders().unwrap_or_default() {
+//!         println!("{:?} (exists: {})", library.path, library.exists);
+//!     }
+//!
+//!     // Mods for Terraria
+//!     for item in steam_dir.get_workshop_items(105600).unwrap_or_default() {
+//!         println!("{} at {:?}", item.item_id, item.path);
+//!     }
+//! }
+//! ```
+//!
+//! Adding a game from another launcher to Steam, e.g. to play it in Big Picture mode:
+//!
+//! ```rust,no_run
+//! use lib_game_detector::steam::{Shortcut, get_steam_dirs};
+//! # let game: lib_game_detector::data::Game = unimplemented!();
+//!
+//! let steam_dir = &get_steam_dirs()[0];
+//! let user = &steam_dir.get_users().unwrap()[0];
+//! let mut writer = steam_dir.get_shortcuts_writer(user.get_account_id()).unwrap();
+//! writer.add_or_update(&Shortcut::from_game(&game));
+//! // Fails if Steam is running, as it would overwrite the changes
+//! writer.save().unwrap();
+//! ```
+
+mod compat_tools;
+mod library_cache;
+mod library_folders;
+mod shortcuts_writer;
 mod steam_base;
 mod steam_shortcuts;
+mod users;
+mod workshop;
 
 use std::{
+    collections::HashSet,
+    env,
     fmt::Display,
+    fs::{read_dir, read_to_string},
     path::{Path, PathBuf},
     process::Command,
 };
 
-pub use steam_base::Steam;
-pub use steam_shortcuts::SteamShortcuts;
-use tracing::{debug, error};
+pub use library_folders::LibraryFolder;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+pub use shortcuts_writer::{Shortcut, ShortcutImages, ShortcutsWriter};
+pub(crate) use steam_base::Steam;
+pub(crate) use steam_shortcuts::SteamShortcuts;
+use tracing::debug;
+pub use users::SteamUser;
+pub use workshop::WorkshopItem;
 
 use crate::{
-    data::SupportedLaunchers,
-    utils::{get_launch_command, get_launch_command_flatpak},
+    data::{InstallVariant, SupportedLaunchers},
+    error::GamesParsingError,
+    linux::sandbox::{HostDirs, is_flatpak_sandbox},
+    utils::{get_launch_command, get_launch_command_flatpak, get_launch_command_snap},
 };
 
-fn get_steam_launch_command(app_id: impl Display, is_using_flatpak: bool) -> Command {
-    let game_run_arg = format!("steam://rungameid/{app_id}");
-    let args = [game_run_arg.as_str()];
-    if is_using_flatpak {
-        get_launch_command_flatpak("com.valvesoftware.Steam", [], args, [])
-    } else {
-        get_launch_command("steam", args, [])
+/// Where a Steam directory was found. Variants are in the order that locations are searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SteamDirSource {
+    /// Given by the user, e.g. with
+    /// [`DetectorOptions::steam_dirs`](crate::data::DetectorOptions::steam_dirs).
+    Custom,
+    /// The `STEAM_DIR` environment variable.
+    SteamDirEnv,
+    /// The `STEAM_COMPAT_CLIENT_INSTALL_PATH` environment variable, which Steam sets for the games
+    /// and compatibility tools it runs.
+    CompatClientInstallPathEnv,
+    /// The `~/.steam/root` symlink.
+    RootSymlink,
+    /// The `~/.steam/steam` symlink.
+    SteamSymlink,
+    /// `~/.steam/debian-installation`, used by Debian's and Ubuntu's Steam packages.
+    DebianInstallation,
+    /// `$XDG_DATA_HOME/Steam`.
+    DataDir,
+    /// The data directory of the Steam flatpak.
+    Flatpak,
+    /// The data directory of the Steam snap.
+    Snap,
+}
+
+/// A Steam installation, i.e. the main Steam directory containing `steamapps`, `userdata`, etc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SteamDir {
+    /// Path to the main Steam directory, e.g. `~/.local/share/Steam`.
+    pub path: PathBuf,
+    /// How Steam was installed.
+    pub install_variant: InstallVariant,
+    /// Where the directory was found.
+    pub source: SteamDirSource,
+}
+
+impl SteamDir {
+    /// Create a [`SteamDir`] for the Steam directory at the given path, e.g. one in a custom
+    /// prefix.
+    #[must_use]
+    pub fn new(path: PathBuf, install_variant: InstallVariant) -> Self {
+        Self {
+            path,
+            install_variant,
+            source: SteamDirSource::Custom,
+        }
+    }
+
+    /// Returns `true` if this is a valid Steam directory, i.e. it contains `libraryfolders.vdf`.
+    #[must_use]
+    pub fn is_detected(&self) -> bool {
+        self.path.is_dir()
+            && get_steamapps_dir(&self.path)
+                .join("libraryfolders.vdf")
+                .is_file()
+    }
+
+    /// Get all library folders listed in `libraryfolders.vdf`, including those which don't exist,
+    /// e.g. because they are on drives which aren't currently mounted.
+    pub fn get_library_folders(&self) -> Result<Vec<LibraryFolder>, GamesParsingError> {
+        library_folders::get_library_folders(&self.path)
+    }
+
+    /// Get all Steam accounts which have logged in on this system, starting with the one which
+    /// logged in most recently.
+    pub fn get_users(&self) -> Result<Vec<SteamUser>, GamesParsingError> {
+        users::get_users(&self.path)
+    }
+
+    /// Get the Workshop items subscribed to for the app with the given ID, from whichever
+    /// existing libraries it is installed in.
+    pub fn get_workshop_items(&self, app_id: u32) -> Result<Vec<WorkshopItem>, GamesParsingError> {
+        let mut items = Vec::new();
+        for library in self.get_library_folders()?.iter().filter(|l| l.exists) {
+            items.extend(workshop::get_workshop_items(&library.path, app_id)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Get a command which launches the app with the given ID through Steam's `-applaunch`
+    /// option, passing the given arguments to the game. Launch options set by the user (see
+    /// [`GameMetadata::launch_options`]) are still applied by Steam, with the arguments added
+    /// after them.
+    ///
+    /// [`GameMetadata::launch_options`]: crate::data::GameMetadata::launch_options
+    #[must_use]
+    pub fn get_applaunch_command<'a>(
+        &self,
+        app_id: u32,
+        args: impl IntoIterator<Item = &'a str>,
+    ) -> Command {
+        let app_id = app_id.to_string();
+        let mut command = get_steam_command(["-applaunch", app_id.as_str()], &self.install_variant);
+        command.args(args);
+
+        command
+    }
+
+    /// Get a [`ShortcutsWriter`] for the non-Steam games of the user with the given ID, i.e. the
+    /// name of their directory in `userdata`.
+    pub fn get_shortcuts_writer(&self, user_id: u32) -> Result<ShortcutsWriter, GamesParsingError> {
+        ShortcutsWriter::new(&self.path.join("userdata").join(user_id.to_string()))
     }
 }
 
-fn get_steam_dir(path_home: &Path, path_data: &Path) -> PathBuf {
-    use SupportedLaunchers::Steam;
+/// Returns `true` if the Steam client is running. Only processes visible to the current one are
+/// checked, so Steam can't be detected from inside a flatpak sandbox.
+#[must_use]
+pub fn is_steam_running() -> bool {
+    let Ok(entries) = read_dir("/proc") else {
+        return false;
+    };
+
+    entries
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        .any(|e| read_to_string(e.path().join("comm")).is_ok_and(|comm| comm.trim() == "steam"))
+}
+
+/// Get every location which is searched for Steam, in order, whether or not Steam was found
+/// there, e.g. for reporting where it was looked for. Several locations may point to the same
+/// directory, e.g. `~/.steam/root` is usually a symlink to `~/.local/share/Steam`.
+#[must_use]
+pub fn get_steam_dir_candidates() -> Vec<SteamDir> {
+    let HostDirs {
+        path_home,
+        path_data,
+        ..
+    } = HostDirs::new(is_flatpak_sandbox());
 
-    // Try following ~/.steam/{root,steam} symlinks and only use
-    // $XDG_DATA_HOME/Steam as a fallback, since it's not reliable.
+    find_steam_dir_candidates(&path_home, &path_data, &[], |key| env::var(key).ok())
+}
+
+/// Get all distinct Steam directories detected on the system, in the order they are searched (see
+/// [`get_steam_dir_candidates`]).
+#[must_use]
+pub fn get_steam_dirs() -> Vec<SteamDir> {
+    get_distinct_steam_dirs(get_steam_dir_candidates())
+        .into_iter()
+        .filter(SteamDir::is_detected)
+        .collect()
+}
+
+/// Get the locations to search for Steam in, starting with the given custom directories and
+/// those given by environment variables
+pub(crate) fn find_steam_dir_candidates(
+    path_home: &Path,
+    path_data: &Path,
+    paths_custom: &[PathBuf],
+    get_env: impl Fn(&str) -> Option<String>,
+) -> Vec<SteamDir> {
+    let native = |path: PathBuf, source: SteamDirSource| SteamDir {
+        path,
+        install_variant: InstallVariant::Native,
+        source,
+    };
+
+    let mut candidates = paths_custom
+        .iter()
+        .map(|path| native(path.clone(), SteamDirSource::Custom))
+        .collect::<Vec<_>>();
+
+    candidates.extend(
+        [
+            ("STEAM_DIR", SteamDirSource::SteamDirEnv),
+            (
+                "STEAM_COMPAT_CLIENT_INSTALL_PATH",
+                SteamDirSource::CompatClientInstallPathEnv,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, source)| {
+            let path = get_env(key).filter(|p| !p.is_empty())?;
+            Some(native(PathBuf::from(path), source))
+        }),
+    );
+
+    // Prefer the ~/.steam/{root,steam} symlinks, using $XDG_DATA_HOME/Steam only afterwards since
+    // it's not reliable.
     // See: https://github.com/Rolv-Apneseth/lib_game_detector/issues/45
-    let is_valid = |p: &Path| p.is_symlink() && p.is_dir();
-    let mut symlink = path_home.join(".steam/root");
-    if !is_valid(&symlink) {
-        symlink = path_home.join(".steam/steam");
+    candidates.extend([
+        native(
+            resolve_symlink(path_home.join(".steam/root")),
+            SteamDirSource::RootSymlink,
+        ),
+        native(
+            resolve_symlink(path_home.join(".steam/steam")),
+            SteamDirSource::SteamSymlink,
+        ),
+        native(
+            path_home.join(".steam/debian-installation"),
+            SteamDirSource::DebianInstallation,
+        ),
+        native(path_data.join("Steam"), SteamDirSource::DataDir),
+        SteamDir {
+            path: path_home.join(".var/app/com.valvesoftware.Steam/data/Steam"),
+            install_variant: InstallVariant::Flatpak,
+            source: SteamDirSource::Flatpak,
+        },
+        SteamDir {
+            path: path_home.join("snap/steam/common/.local/share/Steam"),
+            install_variant: InstallVariant::Snap,
+            source: SteamDirSource::Snap,
+        },
+    ]);
+
+    candidates
+}
+
+/// Remove any Steam directories which are the same as an earlier one, e.g. a symlink to it
+pub(crate) fn get_distinct_steam_dirs(candidates: Vec<SteamDir>) -> Vec<SteamDir> {
+    use SupportedLaunchers::Steam;
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|steam_dir| {
+            let is_distinct = seen.insert(
+                steam_dir
+                    .path
+                    .canonicalize()
+                    .unwrap_or_else(|_| steam_dir.path.clone()),
+            );
+
+            debug!(
+                "{Steam} - Steam directory from {:?} at {:?} (detected: {}, distinct: {is_distinct})",
+                steam_dir.source,
+                steam_dir.path,
+                steam_dir.is_detected()
+            );
+
+            is_distinct
+        })
+        .collect()
+}
+
+/// Returns the path the given symlink points to, or the path itself if it isn't a valid symlink
+fn resolve_symlink(path: PathBuf) -> PathBuf {
+    if !path.is_symlink() {
+        return path;
     }
-    if !is_valid(&symlink) {
-        debug!("{Steam} - Could not find symlinked directories, using fallback");
-        return path_data.join("Steam");
+
+    path.canonicalize().unwrap_or(path)
+}
+
+fn get_steam_launch_command(app_id: impl Display, install_variant: &InstallVariant) -> Command {
+    let game_run_arg = format!("steam://rungameid/{app_id}");
+    get_steam_command([game_run_arg.as_str()], install_variant)
+}
+
+/// Get the command for running Steam with the given arguments
+fn get_steam_command<'a>(
+    args: impl IntoIterator<Item = &'a str>,
+    install_variant: &InstallVariant,
+) -> Command {
+    match install_variant {
+        InstallVariant::Native => get_launch_command("steam", args, []),
+        InstallVariant::Flatpak => {
+            get_launch_command_flatpak("com.valvesoftware.Steam", [], args, [])
+        }
+        InstallVariant::Snap => get_launch_command_snap("steam", args, []),
+        InstallVariant::AppImage(path) | InstallVariant::Custom(path) => {
+            get_launch_command(path, args, [])
+        }
     }
+}
+
+/// Used for getting the path to the "steamapps" directory, which can be capitalised on some systems.
+#[tracing::instrument(level = "trace")]
+fn get_steamapps_dir(path_parent_dir: &Path) -> PathBuf {
+    let path_steamapps_dir = path_parent_dir.join("Steamapps");
 
-    symlink.canonicalize().unwrap_or_else(|e| {
-        error!("{Steam} - Could not canonicalize symlink, using fallback: {e}");
-        path_data.join("Steam")
-    })
+    // Use the capitalised version of directory if it exists
+    if path_steamapps_dir.is_dir() {
+        path_steamapps_dir
+    }
+    // Otherwise proceed with the default
+    else {
+        path_parent_dir.join("steamapps")
+    }
 }
 
-fn get_steam_flatpak_dir(path_home: &Path) -> PathBuf {
-    path_home.join(".var/app/com.valvesoftware.Steam/data/Steam")
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use test_case::test_case;
+
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test]
+    fn test_find_steam_dir_candidates() {
+        let path_file_system_mock = get_mock_file_system_path();
+        let path_home = path_file_system_mock.join("steam_symlinks");
+        let path_data = path_file_system_mock.join("invalid/data/path");
+        let path_custom =
+            path_file_system_mock.join("snap_home/snap/steam/common/.local/share/Steam");
+        let path_env = path_file_system_mock.join(".local/share/Steam");
+
+        let candidates = find_steam_dir_candidates(
+            &path_home,
+            &path_data,
+            std::slice::from_ref(&path_custom),
+            |key| (key == "STEAM_DIR").then(|| path_env.to_string_lossy().into_owned()),
+        );
+        assert_eq!(
+            candidates.iter().map(|d| d.source).collect::<Vec<_>>(),
+            [
+                SteamDirSource::Custom,
+                SteamDirSource::SteamDirEnv,
+                SteamDirSource::RootSymlink,
+                SteamDirSource::SteamSymlink,
+                SteamDirSource::DebianInstallation,
+                SteamDirSource::DataDir,
+                SteamDirSource::Flatpak,
+                SteamDirSource::Snap,
+            ]
+        );
+
+        // The symlink is resolved, so it is the same directory as the Debian installation
+        let path_debian_installation = path_home.join(".steam/debian-installation");
+        assert_eq!(candidates[2].path, path_debian_installation);
+
+        let steam_dirs = get_distinct_steam_dirs(candidates)
+            .into_iter()
+            .filter(SteamDir::is_detected)
+            .map(|d| (d.source, d.path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steam_dirs,
+            [
+                (SteamDirSource::Custom, path_custom),
+                (SteamDirSource::SteamDirEnv, path_env),
+                (SteamDirSource::RootSymlink, path_debian_installation),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_steam_dir_candidates_empty_env() {
+        let path_file_system_mock = get_mock_file_system_path();
+        let candidates = find_steam_dir_candidates(
+            &path_file_system_mock,
+            &path_file_system_mock.join(".local/share"),
+            &[],
+            |_| Some(String::new()),
+        );
+
+        assert_eq!(candidates[0].source, SteamDirSource::RootSymlink);
+        assert_eq!(
+            get_distinct_steam_dirs(candidates)
+                .into_iter()
+                .filter(SteamDir::is_detected)
+                .map(|d| (d.source, d.install_variant))
+                .collect::<Vec<_>>(),
+            [
+                (SteamDirSource::DataDir, InstallVariant::Native),
+                (SteamDirSource::Flatpak, InstallVariant::Flatpak),
+            ]
+        );
+    }
+
+    #[test_case(InstallVariant::Native, "steam", &["-applaunch", "620", "-novid", "+map test"]; "native")]
+    #[test_case(
+        InstallVariant::Flatpak,
+        "flatpak",
+        &["run", "com.valvesoftware.Steam", "-applaunch", "620", "-novid", "+map test"];
+        "flatpak"
+    )]
+    #[test_case(
+        InstallVariant::Snap,
+        "snap",
+        &["run", "steam", "-applaunch", "620", "-novid", "+map test"];
+        "snap"
+    )]
+    fn test_get_applaunch_command(install_variant: InstallVariant, program: &str, args: &[&str]) {
+        let steam_dir = SteamDir::new(PathBuf::from("/steam"), install_variant);
+        let command = steam_dir.get_applaunch_command(620, ["-novid", "+map test"]);
+
+        assert_eq!(command.get_program(), program);
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            args.iter().map(OsStr::new).collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,641 @@
+// PATHS:
+// - <steam dir>/userdata/<user id>/config/shortcuts.vdf
+// - <steam dir>/userdata/<user id>/config/grid/
+use std::{
+    ffi::OsStr,
+    fs::{copy, create_dir_all, read, remove_file, rename, write},
+    path::{Path, PathBuf},
+};
+
+use steam_shortcuts_util::app_id_generator::calculate_app_id;
+use tracing::{debug, trace, warn};
+
+use super::{is_steam_running, steam_shortcuts::get_shortcut_app_id};
+use crate::{
+    data::{Game, SupportedLaunchers},
+    error::GamesParsingError,
+    macros::logs::debug_path,
+    vdf::{self, KeyValues},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::SteamShortcuts;
+/// Keys of `shortcuts.vdf` entries which hold integers rather than strings, used to write the values
+/// set by [`ShortcutsWriter`]. Values parsed from the file keep their own type.
+const INT_KEYS: [&str; 8] = [
+    "appid",
+    "IsHidden",
+    "AllowDesktopConfig",
+    "AllowOverlay",
+    "OpenVR",
+    "Devkit",
+    "DevkitOverrideAppID",
+    "LastPlayTime",
+];
+/// Suffixes Steam expects after the app ID in the names of each image of a shortcut: box art,
+/// capsule, hero, logo and icon
+const IMAGE_SUFFIXES: [&str; 5] = ["p", "", "_hero", "_logo", "_icon"];
+
+/// Images to copy into Steam's `grid` directory for a shortcut. Each is named after the
+/// shortcut's app ID, keeping the extension of the original file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShortcutImages {
+    /// Vertical box art shown in the library, saved as `<app id>p`.
+    pub box_art: Option<PathBuf>,
+    /// Horizontal capsule shown for recently played games, saved as `<app id>`.
+    pub capsule: Option<PathBuf>,
+    /// Wide banner shown at the top of the game's page, saved as `<app id>_hero`.
+    pub hero: Option<PathBuf>,
+    /// Logo shown on top of the hero, saved as `<app id>_logo`.
+    pub logo: Option<PathBuf>,
+    /// Icon, saved as `<app id>_icon` and also set as the shortcut's icon.
+    pub icon: Option<PathBuf>,
+}
+
+impl ShortcutImages {
+    /// Get each image to copy, along with the name Steam expects for it
+    fn get_images(&self, app_id: u32) -> impl Iterator<Item = (&Path, String)> {
+        [
+            &self.box_art,
+            &self.capsule,
+            &self.hero,
+            &self.logo,
+            &self.icon,
+        ]
+        .into_iter()
+        .zip(IMAGE_SUFFIXES)
+        .filter_map(move |(path, suffix)| Some((path.as_deref()?, format!("{app_id}{suffix}"))))
+    }
+}
+
+/// A non-Steam game to add to Steam as a shortcut.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Name of the shortcut shown in Steam.
+    pub title: String,
+    /// Executable (or command) to run.
+    pub executable: PathBuf,
+    /// Directory to start the game in.
+    pub start_dir: Option<PathBuf>,
+    /// Arguments to run the executable with. Steam replaces `%command%` with the executable, so
+    /// environment variables can be set with e.g. `VAR=value %command% --arg`.
+    pub launch_options: String,
+    /// Tags to add the shortcut to, e.g. `favorite`. When updating a shortcut, these are added to
+    /// its existing tags, so collections set through Steam are kept.
+    pub tags: Vec<String>,
+    /// Images to use for the shortcut.
+    pub images: ShortcutImages,
+}
+
+impl Shortcut {
+    /// Create a [`Shortcut`] which launches the given game the same way as its launch command,
+    /// using its box art and icon.
+    #[must_use]
+    pub fn from_game(game: &Game) -> Self {
+        let command = &game.launch_command;
+        let args = command
+            .get_args()
+            .map(|a| quote_arg(&a.to_string_lossy()))
+            .collect::<Vec<_>>();
+        let env_vars = command
+            .get_envs()
+            .filter_map(|(k, v)| {
+                Some(format!(
+                    "{}={}",
+                    k.to_string_lossy(),
+                    quote_arg(&v?.to_string_lossy())
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let launch_options = if env_vars.is_empty() {
+            args.join(" ")
+        } else {
+            [env_vars, vec![String::from("%command%")], args]
+                .concat()
+                .join(" ")
+        };
+
+        Shortcut {
+            title: game.title.clone(),
+            executable: PathBuf::from(command.get_program()),
+            start_dir: command
+                .get_current_dir()
+                .map(Path::to_path_buf)
+                .or_else(|| game.metadata.path_working_dir.clone())
+                .or_else(|| game.path_game_dir.clone()),
+            launch_options,
+            // Steam uses the `favorite` tag for its favourites collection
+            tags: game
+                .metadata
+                .is_favorite
+                .then(|| String::from("favorite"))
+                .into_iter()
+                .chain(game.metadata.collections.iter().cloned())
+                .collect(),
+            images: ShortcutImages {
+                box_art: game.path_box_art.clone(),
+                icon: game.path_icon.clone(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get the value of the `Exe` field, which Steam wraps in quotes
+    fn get_exe(&self) -> String {
+        format!("\"{}\"", self.executable.display())
+    }
+
+    /// Get the app ID Steam assigns to this shortcut, which is used for its images, prefix and
+    /// `steam://rungameid/` link.
+    #[must_use]
+    pub fn get_app_id(&self) -> u32 {
+        calculate_app_id(&self.get_exe(), &self.title)
+    }
+}
+
+/// Quote an argument for use in a shortcut's launch options, if it contains special characters
+fn quote_arg(arg: &str) -> String {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_=+:,./@%".contains(c));
+    if is_plain {
+        return arg.to_owned();
+    }
+
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Adds, updates and removes the non-Steam games of a Steam user, by editing their
+/// `shortcuts.vdf`. Changes are only written to disk by [`ShortcutsWriter::save`].
+#[derive(Debug)]
+pub struct ShortcutsWriter {
+    path_shortcuts: PathBuf,
+    path_grid_dir: PathBuf,
+    /// Entries of `shortcuts.vdf`, kept as they were parsed so that unknown fields are preserved
+    shortcuts: Vec<KeyValues>,
+    /// Images to copy on save, and the names to copy them to
+    images_to_copy: Vec<(PathBuf, String)>,
+    /// Names of images to delete on save
+    images_to_remove: Vec<String>,
+}
+
+impl ShortcutsWriter {
+    /// Load the shortcuts of the Steam user with the given `userdata` directory, i.e.
+    /// `<steam dir>/userdata/<user id>`. A user without any shortcuts yet is valid.
+    #[tracing::instrument(level = "trace")]
+    pub fn new(path_user_dir: &Path) -> Result<Self, GamesParsingError> {
+        if !path_user_dir.is_dir() {
+            return Err(GamesParsingError::Other(format!(
+                "Steam user directory not found: {path_user_dir:?}"
+            )));
+        }
+
+        let path_config = path_user_dir.join("config");
+        let path_shortcuts = path_config.join("shortcuts.vdf");
+
+        debug_path!("shortcuts.vdf", path_shortcuts);
+
+        let shortcuts = if path_shortcuts.is_file() {
+            vdf::parse_binary(&read(&path_shortcuts)?)?
+                .get_object("shortcuts")
+                .into_iter()
+                .flat_map(KeyValues::iter)
+                .filter_map(|(_, s)| s.as_object().cloned())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            path_shortcuts,
+            path_grid_dir: path_config.join("grid"),
+            shortcuts,
+            images_to_copy: vec![],
+            images_to_remove: vec![],
+        })
+    }
+
+    /// Get the app IDs of all shortcuts.
+    #[must_use]
+    pub fn get_app_ids(&self) -> Vec<u32> {
+        self.shortcuts
+            .iter()
+            .map(|s| get_shortcut_app_id(s).0)
+            .collect()
+    }
+
+    /// Add the given shortcut, or update the existing one with the same app ID, returning its app
+    /// ID. An existing shortcut keeps any settings changed through Steam. Shortcuts are only
+    /// matched by app ID, as Steam allows several shortcuts with the same title.
+    pub fn add_or_update(&mut self, shortcut: &Shortcut) -> u32 {
+        let new_app_id = shortcut.get_app_id();
+        let existing = self
+            .shortcuts
+            .iter()
+            .position(|s| get_shortcut_app_id(s).0 == new_app_id);
+
+        let (app_id, entry) = match existing {
+            Some(i) => {
+                let entry = &mut self.shortcuts[i];
+                let (app_id, _) = get_shortcut_app_id(entry);
+                // Older entries don't include the app ID, which would change with the executable
+                entry.set("appid", app_id.to_string());
+                (app_id, entry)
+            }
+            None => {
+                self.shortcuts.push(get_new_entry(new_app_id));
+                (
+                    new_app_id,
+                    self.shortcuts.last_mut().expect("entry was just added"),
+                )
+            }
+        };
+        trace!(
+            "{LAUNCHER} - Writing shortcut '{}' ({app_id})",
+            shortcut.title
+        );
+
+        entry.set("AppName", shortcut.title.as_str());
+        entry.set("Exe", shortcut.get_exe());
+        entry.set(
+            "StartDir",
+            shortcut
+                .start_dir
+                .as_ref()
+                .map(|p| format!("\"{}\"", p.display()))
+                .unwrap_or_default(),
+        );
+        entry.set("LaunchOptions", shortcut.launch_options.as_str());
+        let tags = get_merged_tags(entry.get_object("tags"), &shortcut.tags);
+        entry.set("tags", tags);
+
+        if let Some(path_icon) = &shortcut.images.icon {
+            let path_icon =
+                get_image_path(&self.path_grid_dir, &format!("{app_id}_icon"), path_icon);
+            entry.set("icon", path_icon.to_string_lossy().as_ref());
+        }
+        self.images_to_copy.extend(
+            shortcut
+                .images
+                .get_images(app_id)
+                .map(|(path, name)| (path.to_path_buf(), name)),
+        );
+
+        app_id
+    }
+
+    /// Remove the shortcut with the given app ID, along with its images. Returns `false` if there
+    /// is no such shortcut.
+    pub fn remove(&mut self, app_id: u32) -> bool {
+        let len = self.shortcuts.len();
+        self.shortcuts
+            .retain(|s| get_shortcut_app_id(s).0 != app_id);
+        if self.shortcuts.len() == len {
+            return false;
+        }
+
+        self.images_to_copy
+            .retain(|(_, name)| !is_image_of(name, app_id));
+        self.images_to_remove
+            .extend(IMAGE_SUFFIXES.map(|suffix| format!("{app_id}{suffix}")));
+
+        true
+    }
+
+    /// Write all changes to disk, after backing up the existing `shortcuts.vdf` to
+    /// `shortcuts.vdf.bak`, replacing any previous backup. Fails if Steam is running, as it would
+    /// overwrite the changes when it exits.
+    pub fn save(&mut self) -> Result<(), GamesParsingError> {
+        if is_steam_running() {
+            return Err(GamesParsingError::Other(String::from(
+                "Steam must be closed before its shortcuts can be changed",
+            )));
+        }
+
+        self.write()
+    }
+
+    /// Write all changes to disk, without checking if Steam is running
+    #[tracing::instrument(level = "trace")]
+    fn write(&mut self) -> Result<(), GamesParsingError> {
+        if self.path_shortcuts.is_file() {
+            let path_backup = self.path_shortcuts.with_extension("vdf.bak");
+
+            debug!("{LAUNCHER} - Backing up shortcuts.vdf to {path_backup:?}");
+            copy(&self.path_shortcuts, path_backup)?;
+        } else if let Some(path_config) = self.path_shortcuts.parent() {
+            create_dir_all(path_config)?;
+        }
+
+        let mut shortcuts = KeyValues::default();
+        for (i, shortcut) in self.shortcuts.iter().enumerate() {
+            shortcuts.set(&i.to_string(), shortcut.clone());
+        }
+        let mut root = KeyValues::default();
+        root.set("shortcuts", shortcuts);
+
+        // Write to a temporary file first, so a failed write can't leave a corrupt file behind
+        let path_tmp = self.path_shortcuts.with_extension("vdf.tmp");
+        write(&path_tmp, vdf::to_binary(&root, &INT_KEYS))?;
+        rename(&path_tmp, &self.path_shortcuts)?;
+
+        if !self.images_to_copy.is_empty() {
+            create_dir_all(&self.path_grid_dir)?;
+        }
+        for name in self.images_to_remove.drain(..) {
+            remove_images(&self.path_grid_dir, &name)?;
+        }
+        for (path, name) in self.images_to_copy.drain(..) {
+            let path_dest = get_image_path(&self.path_grid_dir, &name, &path);
+            if path == path_dest {
+                continue;
+            }
+
+            // Remove images with other extensions, which Steam may pick instead
+            remove_images(&self.path_grid_dir, &name)?;
+            if let Err(e) = copy(&path, &path_dest) {
+                warn!("{LAUNCHER} - Could not copy image {path:?} to {path_dest:?}: {e}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Create the entry for a new shortcut, with Steam's default settings
+fn get_new_entry(app_id: u32) -> KeyValues {
+    let mut entry = KeyValues::default();
+    entry.set("appid", app_id.to_string());
+    for key in [
+        "AppName",
+        "Exe",
+        "StartDir",
+        "icon",
+        "ShortcutPath",
+        "LaunchOptions",
+    ] {
+        entry.set(key, "");
+    }
+    for (key, value) in [
+        ("IsHidden", "0"),
+        ("AllowDesktopConfig", "1"),
+        ("AllowOverlay", "1"),
+        ("OpenVR", "0"),
+        ("Devkit", "0"),
+        ("DevkitGameID", ""),
+        ("DevkitOverrideAppID", "0"),
+        ("LastPlayTime", "0"),
+        ("FlatpakAppID", ""),
+    ] {
+        entry.set(key, value);
+    }
+
+    entry
+}
+
+/// Get the tags of a shortcut, with the given tags added after its existing ones (if they aren't
+/// already included)
+fn get_merged_tags(existing: Option<&KeyValues>, tags: &[String]) -> KeyValues {
+    let mut merged: Vec<&str> = existing
+        .into_iter()
+        .flat_map(KeyValues::iter)
+        .filter_map(|(_, tag)| tag.as_str())
+        .collect();
+    for tag in tags {
+        if !merged.contains(&tag.as_str()) {
+            merged.push(tag);
+        }
+    }
+
+    merged
+        .into_iter()
+        .enumerate()
+        .fold(KeyValues::default(), |mut tags, (i, tag)| {
+            tags.set(&i.to_string(), tag);
+            tags
+        })
+}
+
+/// Returns `true` if the image with the given name (without extension) belongs to the given app
+fn is_image_of(name: &str, app_id: u32) -> bool {
+    name.strip_prefix(&app_id.to_string())
+        .is_some_and(|suffix| IMAGE_SUFFIXES.contains(&suffix))
+}
+
+/// Remove all images with the given name (without extension) from the grid directory
+fn remove_images(path_grid_dir: &Path, name: &str) -> Result<(), GamesParsingError> {
+    for ext in ["png", "jpg", "jpeg"] {
+        let path = path_grid_dir.join(format!("{name}.{ext}"));
+        if path.is_file() {
+            remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the path in the grid directory to copy the given image to, keeping its extension
+fn get_image_path(path_grid_dir: &Path, name: &str, path_image: &Path) -> PathBuf {
+    path_grid_dir
+        .join(name)
+        .with_extension(path_image.extension().unwrap_or(OsStr::new("png")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::remove_dir_all, process::Command};
+
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        data::{GameMetadata, InstallVariant},
+        linux::test_utils::get_mock_file_system_path,
+    };
+
+    #[test_case("--fullscreen", "--fullscreen")]
+    #[test_case("", r#""""#)]
+    #[test_case("My Game", r#""My Game""#)]
+    #[test_case(r#"say "hi" $HOME"#, r#""say \"hi\" \$HOME""#)]
+    fn test_quote_arg(arg: &str, expected: &str) {
+        assert_eq!(quote_arg(arg), expected);
+    }
+
+    #[test]
+    fn test_shortcut_from_game() {
+        let mut launch_command = Command::new("lutris");
+        launch_command
+            .arg("lutris:rungameid/1")
+            .arg("My Game")
+            .env("DXVK_HUD", "fps");
+
+        let game = Game {
+            title: String::from("My Game"),
+            path_icon: None,
+            path_box_art: Some(PathBuf::from("/box_art.png")),
+            path_game_dir: Some(PathBuf::from("/games/my_game")),
+            launch_command,
+            source: SupportedLaunchers::Lutris,
+            install_variant: InstallVariant::Native,
+            metadata: GameMetadata {
+                collections: vec![String::from("Indie")],
+                is_favorite: true,
+                ..Default::default()
+            },
+        };
+
+        let shortcut = Shortcut::from_game(&game);
+        assert_eq!(shortcut.executable, PathBuf::from("lutris"));
+        assert_eq!(
+            shortcut.launch_options,
+            r#"DXVK_HUD=fps %command% lutris:rungameid/1 "My Game""#
+        );
+        assert_eq!(shortcut.start_dir, game.path_game_dir);
+        assert_eq!(shortcut.tags, ["favorite", "Indie"]);
+        assert_eq!(shortcut.images.box_art, game.path_box_art);
+        assert_eq!(
+            shortcut.get_app_id(),
+            calculate_app_id(r#""lutris""#, "My Game")
+        );
+    }
+
+    #[test]
+    fn test_shortcuts_writer() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let path_user_dir = std::env::temp_dir().join(format!(
+            "lib_game_detector_shortcuts_writer_{}",
+            std::process::id()
+        ));
+        create_dir_all(path_user_dir.join("config"))?;
+        copy(
+            path_file_system_mock
+                .join("steam_shortcuts_only/Steam/userdata/5678/config/shortcuts.vdf"),
+            path_user_dir.join("config/shortcuts.vdf"),
+        )?;
+
+        let mut writer = ShortcutsWriter::new(&path_user_dir)?;
+        let old_app_id = calculate_app_id(r#""/usr/bin/old-game""#, "Old Shortcut");
+        assert_eq!(writer.get_app_ids(), [old_app_id, 3000000001]);
+        let allow_desktop_config = writer.shortcuts[0].get("AllowDesktopConfig").cloned();
+
+        // Matched by app ID, even for older entries which don't include it
+        let path_box_art = path_file_system_mock
+            .join(".local/share/Steam/appcache/librarycache/1493710_library_600x900.jpg");
+        let updated = Shortcut {
+            title: String::from("Old Shortcut"),
+            executable: PathBuf::from("/usr/bin/old-game"),
+            tags: vec![String::from("Indie")],
+            images: ShortcutImages {
+                box_art: Some(path_box_art.clone()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(writer.add_or_update(&updated), old_app_id);
+
+        // A shortcut with the same title but another app ID is added, rather than replacing it
+        let added = Shortcut {
+            title: String::from("New Shortcut"),
+            executable: PathBuf::from("/usr/bin/newer-game"),
+            start_dir: Some(PathBuf::from("/usr/bin")),
+            launch_options: String::from("--windowed"),
+            tags: vec![String::from("Indie")],
+            images: ShortcutImages {
+                icon: Some(path_box_art.clone()),
+                ..Default::default()
+            },
+        };
+        let added_app_id = writer.add_or_update(&added);
+        assert_eq!(added_app_id, added.get_app_id());
+        assert_ne!(added_app_id, 3000000001);
+
+        let removed = Shortcut {
+            title: String::from("Removed Shortcut"),
+            executable: PathBuf::from("/usr/bin/removed-game"),
+            images: ShortcutImages {
+                box_art: Some(path_box_art),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let removed_app_id = writer.add_or_update(&removed);
+        assert!(writer.remove(removed_app_id));
+        assert!(!writer.remove(removed_app_id));
+
+        writer.write()?;
+
+        let mut writer = ShortcutsWriter::new(&path_user_dir)?;
+        assert_eq!(writer.get_app_ids(), [old_app_id, 3000000001, added_app_id]);
+
+        // Settings changed through Steam are kept
+        let updated_entry = &writer.shortcuts[0];
+        assert_eq!(
+            updated_entry.get("AllowDesktopConfig"),
+            allow_desktop_config.as_ref()
+        );
+        assert_eq!(updated_entry.get_str_at(&["tags", "0"]), Some("Indie"));
+
+        // The shortcut with the same title is left as it was
+        let unchanged = &writer.shortcuts[1];
+        assert_eq!(unchanged.get_str("Exe"), Some(r#""/usr/bin/new-game""#));
+        assert_eq!(unchanged.get_str("IsHidden"), Some("1"));
+        assert_eq!(unchanged.get_str("LastPlayTime"), Some("1700000000"));
+
+        let added = &writer.shortcuts[2];
+        assert_eq!(added.get_str("AppName"), Some("New Shortcut"));
+        assert_eq!(added.get_str("StartDir"), Some(r#""/usr/bin""#));
+        assert_eq!(added.get_str("LaunchOptions"), Some("--windowed"));
+        assert_eq!(added.get_str_at(&["tags", "0"]), Some("Indie"));
+        assert_eq!(added.get_str("AllowOverlay"), Some("1"));
+
+        let path_grid_dir = path_user_dir.join("config/grid");
+        let path_icon = path_grid_dir.join(format!("{added_app_id}_icon.jpg"));
+        assert!(path_grid_dir.join(format!("{old_app_id}p.jpg")).is_file());
+        assert!(path_icon.is_file());
+        assert!(
+            !path_grid_dir
+                .join(format!("{removed_app_id}p.jpg"))
+                .exists()
+        );
+        assert_eq!(added.get_str("icon"), path_icon.to_str());
+
+        // Existing tags (e.g. collections set through Steam) are kept, and new ones are added
+        writer.add_or_update(&Shortcut {
+            tags: vec![String::from("Co-op"), String::from("Indie")],
+            ..updated
+        });
+        writer.write()?;
+
+        let writer = ShortcutsWriter::new(&path_user_dir)?;
+        assert_eq!(
+            writer.shortcuts[0]
+                .get_object("tags")
+                .into_iter()
+                .flat_map(KeyValues::iter)
+                .filter_map(|(_, tag)| tag.as_str())
+                .collect::<Vec<_>>(),
+            ["Indie", "Co-op"]
+        );
+
+        // The previous file is backed up, replacing any older backup
+        let backups = std::fs::read_dir(path_user_dir.join("config"))?
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".bak"))
+            .count();
+        assert_eq!(backups, 1);
+
+        remove_dir_all(&path_user_dir)?;
+
+        Ok(())
+    }
+}
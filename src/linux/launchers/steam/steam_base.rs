@@ -1,9 +1,11 @@
 // PATHS:
 // - ~/.local/share/Steam/
 // - Flatpak: ~/.var/app/com.valvesoftware.Steam
+// - Snap: ~/snap/steam/common/.local/share/Steam/
 use std::{
-    fs::{File, read_dir, read_to_string},
-    io::{self, BufRead, BufReader},
+    collections::{BTreeSet, HashMap, HashSet},
+    fs::{read_dir, read_to_string},
+    io,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -14,22 +16,49 @@ use nom::{
     sequence::delimited,
 };
 use tracing::{debug, error, trace, warn};
-use walkdir::WalkDir;
 
-use super::{get_steam_dir, get_steam_flatpak_dir, get_steam_launch_command};
+use super::{
+    SteamDir,
+    compat_tools::CompatTools,
+    get_steam_launch_command, get_steamapps_dir,
+    library_cache::{AppImages, LibraryCache},
+    library_folders::get_library_folders,
+    users::{SteamUsers, UserAppData},
+};
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
-    macros::logs::{debug_fallback_flatpak, debug_path, warn_no_games},
-    parsers::parse_value_json,
-    utils::{clean_game_title, some_if_dir, some_if_file},
+    data::{
+        Depot, Dlc, Game, GameMetadata, GamesResult, InstallState, InstallVariant, Launcher,
+        SteamAccountFilter, SupportedLaunchers,
+    },
+    error::GamesParsingError,
+    macros::logs::{debug_path, warn_no_games},
+    utils::{clean_game_title, some_if_dir},
+    vdf::{
+        self, KeyValues,
+        appinfo::{self, AppInfo, AppType},
+    },
 };
 
+#[derive(Debug)]
 struct ParsableManifestData {
-    app_id: String,
+    app_id: u32,
     title: String,
     install_dir_path: String,
+    install_state: InstallState,
+    depots: Vec<Depot>,
 }
 
+// Flags of `StateFlags` in app manifests, see `EAppState` in the Steamworks SDK
+const STATE_UPDATE_REQUIRED: u32 = 1 << 1;
+const STATE_FULLY_INSTALLED: u32 = 1 << 2;
+const STATE_FILES_MISSING: u32 = 1 << 5;
+const STATE_FILES_CORRUPT: u32 = 1 << 7;
+const STATE_UPDATE_RUNNING: u32 = 1 << 8;
+const STATE_UPDATE_PAUSED: u32 = 1 << 9;
+const STATE_UPDATE_STARTED: u32 = 1 << 10;
+const STATE_UNINSTALLING: u32 = 1 << 11;
+const STATE_DOWNLOADING: u32 = 1 << 20;
+
 const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Steam;
 
 // UTILS --------------------------------------------------------------------------------
@@ -49,40 +78,123 @@ fn matches_manifest_filename(filename: &str) -> bool {
     })
 }
 
+/// Used for decoding the install state of a game from its app manifest
+#[tracing::instrument(level = "trace", skip(app_state))]
+fn get_install_state(app_state: &KeyValues) -> InstallState {
+    let flags = app_state.get_parsed::<u32>("StateFlags").unwrap_or(0);
+    let has_flag = |flag: u32| flags & flag != 0;
+
+    if has_flag(STATE_UNINSTALLING) {
+        return InstallState::Uninstalling;
+    }
+
+    if has_flag(STATE_FILES_MISSING | STATE_FILES_CORRUPT) {
+        return InstallState::FilesMissing;
+    }
+
+    if has_flag(
+        STATE_UPDATE_RUNNING | STATE_UPDATE_STARTED | STATE_UPDATE_PAUSED | STATE_DOWNLOADING,
+    ) {
+        return InstallState::Downloading {
+            bytes_downloaded: app_state.get_parsed("BytesDownloaded").unwrap_or(0),
+            bytes_to_download: app_state.get_parsed("BytesToDownload").unwrap_or(0),
+            is_paused: has_flag(STATE_UPDATE_PAUSED) && !has_flag(STATE_UPDATE_RUNNING),
+        };
+    }
+
+    // A target build which differs from the installed one means an update is queued
+    let build_id = app_state.get_parsed::<u64>("buildid").unwrap_or(0);
+    let target_build_id = app_state.get_parsed::<u64>("TargetBuildID").unwrap_or(0);
+    let is_update_queued = target_build_id != 0 && target_build_id != build_id;
+
+    if has_flag(STATE_UPDATE_REQUIRED) || !has_flag(STATE_FULLY_INSTALLED) || is_update_queued {
+        return InstallState::UpdateRequired;
+    }
+
+    InstallState::Installed
+}
+
 /// Used for parsing relevant game's data from the given app manifest file's contents
 #[tracing::instrument(level = "trace", skip(file_content))]
-fn parse_game_manifest(file_content: &str) -> IResult<&str, ParsableManifestData> {
-    // ID
-    let (file_content, app_id) = parse_value_json(file_content, "appid")?;
-
-    // TITLE
-    let (file_content, title) = parse_value_json(file_content, "name")?;
+fn parse_game_manifest(file_content: &str) -> Option<ParsableManifestData> {
+    let manifest = vdf::parse(file_content)
+        .inspect_err(|e| error!("{LAUNCHER} - Error with parsing Steam app manifest:\n{e}"))
+        .ok()?;
+    let app_state = manifest.get_object("AppState")?;
+
+    Some(ParsableManifestData {
+        app_id: app_state.get_parsed("appid")?,
+        title: clean_game_title(app_state.get_str("name")?),
+        install_dir_path: app_state.get_str("installdir")?.to_owned(),
+        install_state: get_install_state(app_state),
+        depots: get_installed_depots(app_state),
+    })
+}
 
-    // INSTALL_DIR_PATH
-    let (file_content, install_dir_path) = parse_value_json(file_content, "installdir")?;
+/// Used for getting the installed depots listed in an app manifest
+#[tracing::instrument(level = "trace", skip(app_state))]
+fn get_installed_depots(app_state: &KeyValues) -> Vec<Depot> {
+    app_state
+        .get_object("InstalledDepots")
+        .into_iter()
+        .flat_map(KeyValues::iter)
+        .filter_map(|(depot_id, depot)| {
+            let depot = depot.as_object()?;
+            Some(Depot {
+                depot_id: depot_id.parse().ok()?,
+                manifest_id: depot.get_parsed("manifest").unwrap_or(0),
+                size: depot.get_parsed("size").unwrap_or(0),
+                dlc_app_id: depot.get_parsed("dlcappid"),
+            })
+        })
+        .collect()
+}
 
-    Ok((
-        file_content,
-        ParsableManifestData {
-            app_id,
-            title: clean_game_title(title),
-            install_dir_path,
-        },
-    ))
+/// Installed DLC of each app across all libraries, from the depots in the app's manifest or, for DLC
+/// installed with their own manifest, the DLC's parent app in `appinfo.vdf`
+#[derive(Debug, Default)]
+struct InstalledDlc {
+    by_app: HashMap<u32, BTreeSet<u32>>,
 }
 
-/// Used for getting the path to the "steamapps" directory, which can be capitalised on some systems.
-#[tracing::instrument(level = "trace")]
-fn get_steamapps_dir(path_parent_dir: &Path) -> PathBuf {
-    let path_steamapps_dir = path_parent_dir.join("Steamapps");
+impl InstalledDlc {
+    fn new<'a>(
+        manifests: impl IntoIterator<Item = &'a ParsableManifestData>,
+        app_info: Option<&HashMap<u32, AppInfo>>,
+    ) -> Self {
+        let mut by_app = HashMap::<u32, BTreeSet<u32>>::new();
+
+        for manifest in manifests {
+            let dlc_app_ids = manifest.depots.iter().filter_map(|d| d.dlc_app_id);
+            by_app
+                .entry(manifest.app_id)
+                .or_default()
+                .extend(dlc_app_ids);
+
+            if let Some(AppInfo {
+                app_type: AppType::Dlc,
+                parent_app_id: Some(parent_app_id),
+                ..
+            }) = app_info.and_then(|apps| apps.get(&manifest.app_id))
+            {
+                by_app
+                    .entry(*parent_app_id)
+                    .or_default()
+                    .insert(manifest.app_id);
+            }
+        }
 
-    // Use the capitalised version of directory if it exists
-    if path_steamapps_dir.is_dir() {
-        path_steamapps_dir
+        Self { by_app }
     }
-    // Otherwise proceed with the default
-    else {
-        path_parent_dir.join("steamapps")
+
+    /// Returns `true` if the app is installed DLC of any other app
+    fn is_dlc(&self, app_id: u32) -> bool {
+        self.by_app.values().any(|dlc| dlc.contains(&app_id))
+    }
+
+    /// Returns the app IDs of the installed DLC of the given app
+    fn get_dlc(&self, app_id: u32) -> impl Iterator<Item = u32> + '_ {
+        self.by_app.get(&app_id).into_iter().flatten().copied()
     }
 }
 
@@ -91,7 +203,7 @@ fn get_steamapps_dir(path_parent_dir: &Path) -> PathBuf {
 pub struct SteamLibrary<'steamlibrary> {
     path_library: PathBuf,
     path_steam_dir: &'steamlibrary Path,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
 }
 impl<'steamlibrary> SteamLibrary<'steamlibrary> {
     /// Create a new [`SteamLibrary`], returning [`None`] if the given paths do not exist or
@@ -100,12 +212,12 @@ impl<'steamlibrary> SteamLibrary<'steamlibrary> {
     fn new(
         path_steam_dir: &'steamlibrary Path,
         path_library: PathBuf,
-        is_using_flatpak: bool,
+        install_variant: InstallVariant,
     ) -> Option<Self> {
         let lib = Self {
             path_steam_dir,
             path_library,
-            is_using_flatpak,
+            install_variant,
         };
 
         if !lib.is_detected() {
@@ -149,71 +261,58 @@ impl<'steamlibrary> SteamLibrary<'steamlibrary> {
         Ok(manifest_paths)
     }
 
-    /// Get the box art for a specific game, checking several different potential locations.
+    /// Read and parse all app manifest files (`appmanifest_.*.acf`) in this library
     #[tracing::instrument(level = "trace")]
-    fn get_images(&self, app_id: &str) -> (Option<PathBuf>, Option<PathBuf>) {
-        const FILENAME_1: &str = "library_600x900";
-        const FILENAME_2: &str = "library_capsule";
-        const ICON_FILENAME_LEN: usize = 44;
-
-        let path_lib_cache = self.path_steam_dir.join("appcache").join("librarycache");
-
-        // Old library cache structure
-        let mut path_box_art =
-            some_if_file(path_lib_cache.join(format!("{app_id}_{FILENAME_1}.jpg")));
-        let mut path_icon = some_if_file(path_lib_cache.join(format!("{app_id}_icon.jpg")));
-        if path_box_art.is_some() && path_icon.is_some() {
-            return (path_box_art, path_icon);
-        }
+    fn get_manifests(&self) -> Result<Vec<ParsableManifestData>, io::Error> {
+        let manifest_paths = self.get_manifest_paths()?;
 
-        // In newer structures, icons and box art can appear in any sub-dir within the `app_id` dir
-        for res in WalkDir::new(path_lib_cache.join(app_id))
-            .min_depth(1)
-            .max_depth(2)
-            .contents_first(true)
-        {
-            let Ok(dir_entry) = res else {
-                continue;
-            };
-
-            let Some(filename) = dir_entry.file_name().to_str() else {
-                continue;
-            };
-
-            // Don't match by filename exactly, as the name may also be named
-            // differently depending on the language, e.g. 292030_library_600x900_russian.jpg
-            if filename.contains(FILENAME_1) || filename.contains(FILENAME_2) {
-                path_box_art = Some(dir_entry.into_path());
-            }
-            // Not sure how else to parse these, as I can't find them mentioned anywhere.
-            // The filenames look like: a4c7a8cce43d797c275aaf601d6855b90ba87769.jpg
-            else if filename.len() == ICON_FILENAME_LEN && filename.ends_with(".jpg") {
-                path_icon = Some(dir_entry.into_path());
-            }
-        }
+        if manifest_paths.is_empty() {
+            warn!(
+                "{LAUNCHER} - No app manifest files found for steam library: {:?}",
+                self.path_library
+            );
+        };
 
-        (path_box_art, path_icon)
-    }
+        Ok(manifest_paths
+            .iter()
+            .filter_map(|path| {
+                let file_content = read_to_string(path)
+                    .map_err(|e| {
+                        error!("{LAUNCHER} - Error with reading Steam app manifest file at {path:?}:\n{e}");
+                    })
+                    .ok()?;
 
-    /// Returns a new Game from the given path to a steam app manifest file (`appmanifest_.*.acf`)
-    #[tracing::instrument(level = "trace")]
-    fn get_game(&self, path_app_manifest: &PathBuf) -> Option<Game> {
-        let file_content = read_to_string(path_app_manifest)
-            .map_err(|e| {
-                error!("{LAUNCHER} - Error with reading Steam app manifest file at {path_app_manifest:?}:\n{e}");
+                parse_game_manifest(&file_content)
             })
-            .ok()?;
-
-        let (
-            _,
-            ParsableManifestData {
-                app_id,
-                title,
-                install_dir_path,
-            },
-        ) = parse_game_manifest(&file_content).ok()?;
+            .collect())
+    }
+
+    /// Returns a new Game from the given parsed app manifest
+    #[tracing::instrument(level = "trace", skip(app_info, compat_tools, users, installed_dlc))]
+    fn get_game(
+        &self,
+        manifest: ParsableManifestData,
+        app_info: Option<&HashMap<u32, AppInfo>>,
+        compat_tools: &CompatTools,
+        users: &SteamUsers,
+        installed_dlc: &InstalledDlc,
+        library_cache: &LibraryCache,
+    ) -> Option<Game> {
+        let ParsableManifestData {
+            app_id,
+            title,
+            install_dir_path,
+            install_state,
+            depots,
+        } = manifest;
+
+        // DLC with their own manifest are listed under their base game instead
+        if installed_dlc.is_dlc(app_id) {
+            trace!("{LAUNCHER} - Skipped steam title as it is DLC: {title:?}");
+            return None;
+        }
 
-        let launch_command = get_steam_launch_command(&app_id, self.is_using_flatpak);
+        let launch_command = get_steam_launch_command(app_id, &self.install_variant);
 
         let path_game_dir = some_if_dir(
             self.path_library
@@ -221,18 +320,77 @@ impl<'steamlibrary> SteamLibrary<'steamlibrary> {
                 .join(install_dir_path),
         );
 
-        let (path_box_art, path_icon) = self.get_images(&app_id);
+        let user_data = users.get_app_data(app_id);
+
+        // Custom box art set by the user takes priority
+        let AppImages {
+            path_box_art,
+            path_icon,
+        } = library_cache.get_images(app_id);
+        let path_box_art = user_data
+            .as_ref()
+            .and_then(|d| d.path_box_art.clone())
+            .or(path_box_art);
 
         trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
         trace!("{LAUNCHER} - Box art for '{title}': {path_box_art:?}");
         trace!("{LAUNCHER} - Icon for '{title}': {path_icon:?}");
 
-        // Skip entries without box art as they are not games (runtimes, redistributables, DLC, etc.),
-        // at least as far as I know
-        if path_box_art.is_none() {
-            trace!("{LAUNCHER} - Skipped steam title as no box art exists for it: {title:?}");
-            return None;
-        }
+        let metadata = match app_info.and_then(|apps| apps.get(&app_id)) {
+            Some(AppInfo { app_type, .. }) if !app_type.is_game() => {
+                trace!(
+                    "{LAUNCHER} - Skipped steam title as it is not a game ({app_type:?}): {title:?}"
+                );
+                return None;
+            }
+            Some(AppInfo { metadata, .. }) => metadata.clone(),
+            // Without appinfo, skip entries without box art as they are likely not games
+            // (runtimes, redistributables, DLC, etc.)
+            None if path_box_art.is_none() => {
+                trace!("{LAUNCHER} - Skipped steam title as no box art exists for it: {title:?}");
+                return None;
+            }
+            None => GameMetadata::default(),
+        };
+
+        let supports_linux = app_info
+            .is_some()
+            .then(|| metadata.supported_os.iter().any(|os| os == "linux"));
+        let compat_tool = compat_tools.get_compat_tool(app_id, &self.path_library, supports_linux);
+
+        let dlc = installed_dlc
+            .get_dlc(app_id)
+            .map(|dlc_app_id| Dlc {
+                id: dlc_app_id.to_string(),
+                name: app_info
+                    .and_then(|apps| apps.get(&dlc_app_id))
+                    .and_then(|a| a.name.clone()),
+            })
+            .collect();
+
+        let (steam_id, user_data) = match user_data {
+            Some(user_data) => (Some(user_data.steam_id), user_data),
+            None => (None, UserAppData::default()),
+        };
+
+        let metadata = GameMetadata {
+            install_state: Some(install_state),
+            dlc,
+            depots,
+            compat_tool,
+            launch_options: user_data.launch_options,
+            collections: user_data.collections,
+            is_favorite: user_data.is_favorite,
+            is_hidden: user_data.is_hidden,
+            last_played: user_data.last_played,
+            playtime: user_data.playtime,
+            screenshots: user_data.screenshots,
+            achievements: user_data.achievements,
+            steam_id,
+            ..metadata
+        };
+
+        trace!("{LAUNCHER} - Metadata for '{title}': {metadata:?}");
 
         Some(Game {
             title,
@@ -241,25 +399,40 @@ impl<'steamlibrary> SteamLibrary<'steamlibrary> {
             path_game_dir,
             path_icon,
             source: LAUNCHER.clone(),
+            install_variant: self.install_variant.clone(),
+            metadata,
         })
     }
 
-    /// Get all steam games associated with this library
-    #[tracing::instrument(level = "trace")]
-    pub fn get_all_games(&self) -> Result<Vec<Game>, io::Error> {
-        let manifest_paths = self.get_manifest_paths()?;
-
-        if manifest_paths.is_empty() {
-            warn!(
-                "{LAUNCHER} - No app manifest files found for steam library: {:?}",
-                self.path_library
-            );
-        };
-
-        Ok(manifest_paths
-            .iter()
-            .filter_map(|path| self.get_game(path))
-            .collect())
+    /// Get all steam games from the given app manifests of this library, using the given parsed
+    /// `appinfo.vdf` (if available) to classify and enrich them, and the given users' data for
+    /// playtime and custom art
+    #[tracing::instrument(
+        level = "trace",
+        skip(manifests, app_info, compat_tools, users, installed_dlc, library_cache)
+    )]
+    fn get_all_games(
+        &self,
+        manifests: Vec<ParsableManifestData>,
+        app_info: Option<&HashMap<u32, AppInfo>>,
+        compat_tools: &CompatTools,
+        users: &SteamUsers,
+        installed_dlc: &InstalledDlc,
+        library_cache: &LibraryCache,
+    ) -> Vec<Game> {
+        manifests
+            .into_iter()
+            .filter_map(|m| {
+                self.get_game(
+                    m,
+                    app_info,
+                    compat_tools,
+                    users,
+                    installed_dlc,
+                    library_cache,
+                )
+            })
+            .collect()
     }
 
     #[tracing::instrument(level = "trace")]
@@ -272,52 +445,76 @@ impl<'steamlibrary> SteamLibrary<'steamlibrary> {
 #[derive(Debug)]
 pub struct Steam {
     path_steam_dir: PathBuf,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
+    steam_accounts: SteamAccountFilter,
+    include_hidden: bool,
 }
 
 impl Steam {
-    pub fn new(path_home: &Path, path_data: &Path) -> Self {
-        let mut path_steam_dir = get_steam_dir(path_home, path_data);
-        let mut is_using_flatpak = false;
-
-        if !path_steam_dir.is_dir() {
-            debug_fallback_flatpak!();
-
-            is_using_flatpak = true;
-            path_steam_dir = get_steam_flatpak_dir(path_home);
-        };
-
+    pub fn new(
+        steam_dir: SteamDir,
+        steam_accounts: SteamAccountFilter,
+        include_hidden: bool,
+    ) -> Self {
+        let SteamDir {
+            path: path_steam_dir,
+            install_variant,
+            source,
+        } = steam_dir;
+
+        debug!("{LAUNCHER} - main Steam directory found from {source:?}");
         debug_path!("main Steam directory", path_steam_dir);
 
         Steam {
             path_steam_dir,
-            is_using_flatpak,
+            install_variant,
+            steam_accounts,
+            include_hidden,
         }
     }
 
-    /// Get all available steam libraries by parsing the `libraryfolders.vdf` file
+    /// Get all available steam libraries by parsing the `libraryfolders.vdf` file, skipping those
+    /// which don't exist (e.g. because their drive isn't mounted)
     #[tracing::instrument(level = "trace")]
-    pub fn get_steam_libraries(&self) -> Result<Vec<SteamLibrary<'_>>, io::Error> {
-        let libraries_vdf_path = get_steamapps_dir(&self.path_steam_dir).join("libraryfolders.vdf");
-
-        debug_path!("libraryfolders.vdf", libraries_vdf_path);
-
-        Ok(BufReader::new(File::open(libraries_vdf_path)?)
-            .lines()
-            .map_while(Result::ok)
-            .filter_map(|line| {
-                parse_value_json(&line, "path")
-                    .ok()
-                    .and_then(|(_, path_library)| {
-                        SteamLibrary::new(
-                            &self.path_steam_dir,
-                            PathBuf::from(path_library),
-                            self.is_using_flatpak,
-                        )
-                    })
+    pub fn get_steam_libraries(&self) -> Result<Vec<SteamLibrary<'_>>, GamesParsingError> {
+        Ok(get_library_folders(&self.path_steam_dir)?
+            .into_iter()
+            .filter(|l| l.exists)
+            .filter_map(|l| {
+                SteamLibrary::new(&self.path_steam_dir, l.path, self.install_variant.clone())
             })
             .collect())
     }
+
+    /// Parse `appinfo.vdf` for all apps (and their installed DLC) with the given manifests,
+    /// returning [`None`] if it is unavailable
+    #[tracing::instrument(level = "trace", skip(manifests))]
+    fn get_app_info<'a>(
+        &self,
+        manifests: impl IntoIterator<Item = &'a ParsableManifestData>,
+    ) -> Option<HashMap<u32, AppInfo>> {
+        let path_app_info = self.path_steam_dir.join("appcache/appinfo.vdf");
+
+        debug_path!("appinfo.vdf", path_app_info);
+
+        if !path_app_info.is_file() {
+            debug!("{LAUNCHER} - appinfo.vdf not found, falling back to checking for box art");
+            return None;
+        }
+
+        // DLC are included for their names
+        let app_ids = manifests
+            .into_iter()
+            .flat_map(|m| {
+                let dlc_app_ids = m.depots.iter().filter_map(|d| d.dlc_app_id);
+                std::iter::once(m.app_id).chain(dlc_app_ids)
+            })
+            .collect::<HashSet<_>>();
+
+        appinfo::parse_file(&path_app_info, |app_id| app_ids.contains(&app_id))
+            .inspect_err(|e| error!("{LAUNCHER} - Error with parsing appinfo.vdf:\n{e}"))
+            .ok()
+    }
 }
 
 impl Launcher for Steam {
@@ -325,6 +522,10 @@ impl Launcher for Steam {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     fn is_detected(&self) -> bool {
         self.path_steam_dir.is_dir()
             && get_steamapps_dir(&self.path_steam_dir)
@@ -345,10 +546,47 @@ impl Launcher for Steam {
 
         debug!("{LAUNCHER} - libraries detected: {:?}", libraries);
 
+        let manifests = libraries
+            .iter()
+            .map(|l| l.get_manifests().ok())
+            .collect::<Vec<_>>();
+
+        let app_info = self.get_app_info(manifests.iter().flatten().flatten());
+        let compat_tools = CompatTools::new(
+            &self.path_steam_dir,
+            &libraries
+                .iter()
+                .map(|l| l.path_library.clone())
+                .collect::<Vec<_>>(),
+        );
+
+        let users = SteamUsers::new(&self.path_steam_dir, &self.steam_accounts);
+
+        // DLC can be installed in a different library than their parent app
+        let installed_dlc =
+            InstalledDlc::new(manifests.iter().flatten().flatten(), app_info.as_ref());
+
+        let app_ids = manifests
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|m| m.app_id)
+            .collect::<HashSet<_>>();
+        let library_cache =
+            LibraryCache::new(&self.path_steam_dir, |app_id| app_ids.contains(&app_id));
+
         let games = libraries
             .into_iter()
-            .filter_map(|l| {
-                let games = l.get_all_games().ok()?;
+            .zip(manifests)
+            .filter_map(|(l, manifests)| {
+                let games = l.get_all_games(
+                    manifests?,
+                    app_info.as_ref(),
+                    &compat_tools,
+                    &users,
+                    &installed_dlc,
+                    &library_cache,
+                );
 
                 trace!(
                     "{LAUNCHER} - games for library at {:?}: {:?}",
@@ -363,7 +601,11 @@ impl Launcher for Steam {
             warn_no_games!();
         }
 
-        Ok(games.into_iter().flatten().collect())
+        Ok(games
+            .into_iter()
+            .flatten()
+            .filter(|g| self.include_hidden || !g.metadata.is_hidden)
+            .collect())
     }
 }
 
@@ -372,20 +614,27 @@ mod tests {
     use test_case::test_case;
 
     use super::*;
-    use crate::{error::GamesParsingError, linux::test_utils::get_mock_file_system_path};
-
-    #[test_case(false, "", ".local/share"; "standard")]
-    #[test_case(false, "steam_symlinks", ""; "standard - fallback steam root")]
-    #[test_case(true, "", "invalid/data/path"; "flatpak")]
-    fn test_steam_launcher(is_testing_flatpak: bool, path_home: &str, path_data: &str) {
+    use crate::{
+        data::Achievements, error::GamesParsingError, linux::test_utils::get_mock_file_system_path,
+    };
+
+    #[test_case(InstallVariant::Native, ".local/share/Steam"; "standard")]
+    #[test_case(InstallVariant::Native, "steam_symlinks/.steam/debian-installation"; "debian")]
+    #[test_case(InstallVariant::Flatpak, ".var/app/com.valvesoftware.Steam/data/Steam"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home/snap/steam/common/.local/share/Steam"; "snap")]
+    fn test_steam_launcher(install_variant: InstallVariant, path_steam_dir: &str) {
         let path_files_system_mock = get_mock_file_system_path();
         let launcher = Steam::new(
-            &path_files_system_mock.join(path_home),
-            &path_files_system_mock.join(path_data),
+            SteamDir::new(
+                path_files_system_mock.join(path_steam_dir),
+                install_variant.clone(),
+            ),
+            SteamAccountFilter::All,
+            true,
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         // Minor test to ensure debug formatting for `SupportedLaunchers` works as intended
         assert_eq!(format!("{:?}", launcher.get_launcher_type()), "Steam");
@@ -405,22 +654,45 @@ mod tests {
             SteamLibrary {
                 path_library: path_libs_dir.join("1"),
                 path_steam_dir,
-                is_using_flatpak: false,
+                install_variant: InstallVariant::Native,
             },
             SteamLibrary {
                 path_library: path_libs_dir.join("2"),
                 path_steam_dir,
-                is_using_flatpak: false,
+                install_variant: InstallVariant::Native,
             },
         ];
 
         assert!(libraries[0].is_detected());
         assert!(libraries[1].is_detected());
 
-        let mut games = [libraries[0].get_all_games()?, libraries[1].get_all_games()?];
+        let manifests = [libraries[0].get_manifests()?, libraries[1].get_manifests()?];
+        let installed_dlc = InstalledDlc::new(manifests.iter().flatten(), None);
+        let library_cache = LibraryCache::new(path_steam_dir, |_| true);
+
+        // Without appinfo, tools with box art are kept and games without box art are skipped
+        let [manifests_1, manifests_2] = manifests;
+        let mut games = [
+            libraries[0].get_all_games(
+                manifests_1,
+                None,
+                &CompatTools::default(),
+                &SteamUsers::default(),
+                &installed_dlc,
+                &library_cache,
+            ),
+            libraries[1].get_all_games(
+                manifests_2,
+                None,
+                &CompatTools::default(),
+                &SteamUsers::default(),
+                &installed_dlc,
+                &library_cache,
+            ),
+        ];
 
         assert_eq!(games[0].len(), 4);
-        assert_eq!(games[1].len(), 3);
+        assert_eq!(games[1].len(), 4);
 
         games[0].sort_by_key(|a| a.title.clone());
         games[1].sort_by_key(|a| a.title.clone());
@@ -430,8 +702,9 @@ mod tests {
         assert_eq!(games[0][2].title, "Unrailed!");
         assert_eq!(games[0][3].title, "Warhammer 40,000: Speed Freeks");
         assert_eq!(games[1][0].title, "Marvel Rivals");
-        assert_eq!(games[1][1].title, "Terraria");
-        assert_eq!(games[1][2].title, "Timberborn");
+        assert_eq!(games[1][1].title, "Proton Experimental");
+        assert_eq!(games[1][2].title, "Terraria");
+        assert_eq!(games[1][3].title, "Timberborn");
 
         assert!(games[0][0].path_icon.is_some());
         assert!(games[0][1].path_icon.is_none());
@@ -440,6 +713,7 @@ mod tests {
         assert!(games[1][0].path_icon.is_none());
         assert!(games[1][1].path_icon.is_none());
         assert!(games[1][2].path_icon.is_none());
+        assert!(games[1][3].path_icon.is_none());
 
         assert!(games[0][1].path_box_art.as_ref().is_some_and(|p| {
             p.file_name()
@@ -450,12 +724,291 @@ mod tests {
                 .is_some_and(|f| f.to_string_lossy() == "library_600x900.jpg")
         }));
 
+        // DLC with their own manifest aren't games, even with box art, and have no names without
+        // appinfo
+        assert_eq!(
+            games[1][2].metadata.dlc,
+            [Dlc {
+                id: String::from("409210"),
+                name: None,
+            }]
+        );
+
         games.into_iter().for_each(|lib| {
             lib.into_iter().for_each(|game| {
                 assert!(game.path_game_dir.is_some());
+                assert!(game.metadata.developer.is_none());
+                assert!(game.metadata.install_state.is_some());
             })
         });
 
         Ok(())
     }
+
+    #[test]
+    fn test_steam_libraries_app_info() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = Steam::new(
+            SteamDir::new(
+                path_file_system_mock.join(".local/share/Steam"),
+                InstallVariant::Native,
+            ),
+            SteamAccountFilter::All,
+            true,
+        );
+        let library = SteamLibrary {
+            path_library: path_file_system_mock.join("steam_libraries/2"),
+            path_steam_dir: &launcher.path_steam_dir,
+            install_variant: InstallVariant::Native,
+        };
+
+        let manifests = library.get_manifests()?;
+        let app_info = launcher.get_app_info(&manifests);
+        assert!(app_info.as_ref().is_some_and(|a| a.len() == 7));
+
+        // Tools are skipped even with box art, and games are kept even without box art
+        let compat_tools = CompatTools::new(
+            &launcher.path_steam_dir,
+            std::slice::from_ref(&library.path_library),
+        );
+        let users = SteamUsers::new(&launcher.path_steam_dir, &SteamAccountFilter::All);
+        let installed_dlc = InstalledDlc::new(&manifests, app_info.as_ref());
+        let library_cache = LibraryCache::new(&launcher.path_steam_dir, |_| true);
+        let mut games = library.get_all_games(
+            manifests,
+            app_info.as_ref(),
+            &compat_tools,
+            &users,
+            &installed_dlc,
+            &library_cache,
+        );
+        games.sort_by_key(|a| a.title.clone());
+
+        assert_eq!(games.len(), 4);
+        assert_eq!(games[0].title, "Marvel Rivals");
+        assert_eq!(games[1].title, "Portal 2");
+        assert_eq!(games[2].title, "Terraria");
+        assert_eq!(games[3].title, "Timberborn");
+
+        assert!(games[1].path_box_art.is_none());
+        assert_eq!(games[1].metadata.developer.as_deref(), Some("Valve"));
+        assert_eq!(games[2].metadata.developer.as_deref(), Some("Re-Logic"));
+        assert_eq!(games[2].metadata.release_date, Some(1305529200));
+        assert_eq!(
+            games[3].metadata.genres,
+            ["Strategy", "Indie", "Simulation", "Early Access"]
+        );
+        assert_eq!(games[3].metadata.supported_os, ["windows", "macos"]);
+
+        // Per-user data comes from the account which played the game most recently
+        assert_eq!(games[0].metadata.steam_id, Some(76561197960270049));
+        assert_eq!(games[0].metadata.playtime, Some(30));
+        assert!(
+            games[0]
+                .path_box_art
+                .as_ref()
+                .is_some_and(|p| p.ends_with("userdata/4321/config/grid/2767030p.png"))
+        );
+        assert_eq!(games[2].metadata.steam_id, Some(76561197960266962));
+        assert_eq!(games[2].metadata.last_played, Some(1600000000));
+        assert_eq!(games[2].metadata.playtime, Some(600));
+        assert!(games[1].metadata.steam_id.is_none());
+        assert!(games[1].metadata.playtime.is_none());
+
+        // DLC are listed from both the depots and the manifests of installed DLC
+        assert_eq!(
+            games[1].metadata.dlc,
+            [Dlc {
+                id: String::from("247120"),
+                name: Some(String::from("Portal 2 Sixense Perceptual Pack")),
+            }]
+        );
+        assert_eq!(
+            games[2].metadata.dlc,
+            [Dlc {
+                id: String::from("409210"),
+                name: Some(String::from("Terraria: Official Soundtrack")),
+            }]
+        );
+        assert_eq!(
+            games[2].metadata.depots,
+            [
+                Depot {
+                    depot_id: 105602,
+                    manifest_id: 6844625161744350207,
+                    size: 715107635,
+                    dlc_app_id: None,
+                },
+                Depot {
+                    depot_id: 409211,
+                    manifest_id: 2394753391346473524,
+                    size: 163987112,
+                    dlc_app_id: Some(409210),
+                },
+            ]
+        );
+        assert!(games[3].metadata.dlc.is_empty());
+
+        // Screenshots and achievements, also from the account which played most recently
+        assert_eq!(
+            games[0].metadata.achievements,
+            Some(Achievements {
+                unlocked: 12,
+                total: 40
+            })
+        );
+        assert!(games[0].metadata.screenshots.is_empty());
+        assert_eq!(
+            games[2].metadata.achievements,
+            Some(Achievements {
+                unlocked: 50,
+                total: 88
+            })
+        );
+        assert_eq!(games[2].metadata.screenshots.len(), 2);
+        assert!(games[1].metadata.achievements.is_none());
+
+        // Collections also come from that account, from its cloud storage or `sharedconfig.vdf`
+        assert_eq!(games[0].metadata.collections, ["Shooters"]);
+        assert!(games[0].metadata.is_favorite);
+        assert!(games[1].metadata.collections.is_empty());
+        assert_eq!(games[2].metadata.collections, ["Platformers"]);
+        assert!(games[2].metadata.is_favorite);
+        assert!(!games[2].metadata.is_hidden);
+        assert!(games[3].metadata.is_hidden);
+        assert_eq!(games[3].metadata.steam_id, Some(76561197960266962));
+
+        assert_eq!(
+            games[0].metadata.install_state,
+            Some(InstallState::UpdateRequired)
+        );
+        assert_eq!(
+            games[2].metadata.install_state,
+            Some(InstallState::Installed)
+        );
+
+        // Proton is only used for games which don't support Linux, unless selected specifically
+        assert!(
+            games[0]
+                .metadata
+                .compat_tool
+                .as_ref()
+                .is_some_and(|t| t.name == "proton_experimental" && t.path_tool.is_some())
+        );
+        assert!(games[1].metadata.compat_tool.is_none());
+        assert!(games[2].metadata.compat_tool.is_none());
+        assert!(
+            games[3]
+                .metadata
+                .compat_tool
+                .as_ref()
+                .is_some_and(|t| t.name == "GE-Proton9-20" && t.path_prefix.is_some())
+        );
+        assert_eq!(
+            games[3].metadata.install_state,
+            Some(InstallState::Downloading {
+                bytes_downloaded: 500000,
+                bytes_to_download: 2000000,
+                is_paused: false,
+            })
+        );
+        assert_eq!(
+            games[3]
+                .metadata
+                .install_state
+                .as_ref()
+                .and_then(InstallState::get_download_progress),
+            Some(0.25)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_installed_dlc_across_libraries() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = Steam::new(
+            SteamDir::new(
+                path_file_system_mock.join(".local/share/Steam"),
+                InstallVariant::Native,
+            ),
+            SteamAccountFilter::All,
+            true,
+        );
+        let library = SteamLibrary {
+            path_library: path_file_system_mock.join("steam_libraries/2"),
+            path_steam_dir: &launcher.path_steam_dir,
+            install_variant: InstallVariant::Native,
+        };
+
+        // Portal 2 and its DLC's own manifest as if they were in different libraries. Without the
+        // DLC depot in Portal 2's manifest, the DLC can only be matched through `appinfo.vdf`.
+        let (dlc_manifests, mut game_manifests): (Vec<_>, Vec<_>) = library
+            .get_manifests()?
+            .into_iter()
+            .filter(|m| m.app_id == 620 || m.app_id == 247120)
+            .partition(|m| m.app_id == 247120);
+        assert_eq!(dlc_manifests.len(), 1);
+        assert_eq!(game_manifests.len(), 1);
+        game_manifests[0].depots.clear();
+
+        let app_info = launcher.get_app_info(game_manifests.iter().chain(&dlc_manifests));
+        let installed_dlc = InstalledDlc::new(
+            game_manifests.iter().chain(&dlc_manifests),
+            app_info.as_ref(),
+        );
+        let library_cache = LibraryCache::new(&launcher.path_steam_dir, |_| true);
+        let get_games = |manifests| {
+            library.get_all_games(
+                manifests,
+                app_info.as_ref(),
+                &CompatTools::default(),
+                &SteamUsers::default(),
+                &installed_dlc,
+                &library_cache,
+            )
+        };
+
+        let games = get_games(game_manifests);
+        assert_eq!(games.len(), 1);
+        assert_eq!(
+            games[0].metadata.dlc,
+            [Dlc {
+                id: String::from("247120"),
+                name: Some(String::from("Portal 2 Sixense Perceptual Pack")),
+            }]
+        );
+        assert!(get_games(dlc_manifests).is_empty());
+
+        Ok(())
+    }
+
+    #[test_case("4", InstallState::Installed; "installed")]
+    #[test_case("6", InstallState::UpdateRequired; "update required")]
+    #[test_case("2", InstallState::UpdateRequired; "partially installed")]
+    #[test_case("4\" \"TargetBuildID\" \"2", InstallState::UpdateRequired; "update queued")]
+    #[test_case("1542", InstallState::Downloading { bytes_downloaded: 10, bytes_to_download: 20, is_paused: true }; "paused")]
+    #[test_case("1048582", InstallState::Downloading { bytes_downloaded: 10, bytes_to_download: 20, is_paused: false }; "downloading")]
+    #[test_case("2052", InstallState::Uninstalling; "uninstalling")]
+    #[test_case("36", InstallState::FilesMissing; "files missing")]
+    #[test_case("132", InstallState::FilesMissing; "files corrupt")]
+    fn test_get_install_state(
+        state_flags: &str,
+        expected: InstallState,
+    ) -> Result<(), GamesParsingError> {
+        let manifest = vdf::parse(&format!(
+            r#""AppState" {{ "StateFlags" "{state_flags}" "buildid" "1" "BytesDownloaded" "10" "BytesToDownload" "20" }}"#
+        ))?;
+        let app_state = manifest
+            .get_object("AppState")
+            .expect("AppState block should exist");
+
+        assert_eq!(get_install_state(app_state), expected);
+        assert_eq!(
+            get_install_state(app_state).is_launchable(),
+            expected == InstallState::Installed
+        );
+
+        Ok(())
+    }
 }
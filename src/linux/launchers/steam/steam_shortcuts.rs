@@ -1,34 +1,48 @@
 use std::{
-    fs::{read, read_dir, read_to_string},
+    fs::read,
     mem,
     path::{Path, PathBuf},
 };
 
 use itertools::Itertools;
-use nom::{
-    IResult, Parser,
-    bytes::complete::{take_till, take_until},
-    character::complete::char,
-    sequence::delimited,
-};
-use steam_shortcuts_util::parse_shortcuts;
+use steam_shortcuts_util::app_id_generator::calculate_app_id;
 use tracing::{error, trace, warn};
 
-use super::{get_steam_dir, get_steam_flatpak_dir, get_steam_launch_command};
+use super::{
+    SteamDir,
+    compat_tools::CompatTools,
+    get_steam_launch_command,
+    library_folders::get_library_folders,
+    users::{get_screenshots, get_userdata_dirs},
+};
 use crate::{
-    data::{Game, GamesResult, Launcher, SupportedLaunchers},
+    data::{
+        Game, GameMetadata, GamesResult, InstallVariant, Launcher, SteamAccountFilter,
+        SupportedLaunchers,
+    },
     error::GamesParsingError,
-    macros::logs::{debug_fallback_flatpak, debug_path, warn_no_games},
-    parsers::{parse_between_double_quotes, parse_not_double_quote},
-    utils::{clean_game_title, get_existing_image_path},
+    macros::logs::{debug_path, warn_no_games},
+    utils::{clean_game_title, get_existing_image_path, some_if_dir, some_if_file},
+    vdf::{self, KeyValues},
 };
 
 /// Data parseable from a Steam user's `shortcuts.vdf`
 #[derive(Debug, Clone, Default)]
 pub struct ParsableShortcutData {
     icon: String,
-    box_art_id: String,
+    /// 32-bit app ID of the shortcut, which is also used for its box art and prefix
+    app_id: u32,
+    /// Whether the app ID had to be computed, as older entries don't include it
+    is_app_id_computed: bool,
     title: String,
+    exe: String,
+    start_dir: String,
+    launch_options: String,
+    is_hidden: bool,
+    is_vr: bool,
+    tags: Vec<String>,
+    /// Unix timestamp of when the shortcut was last launched, which is `0` if it never was
+    last_play_time: u32,
 }
 
 /// Data parseable from a Steam user's `screenshots.vdf`
@@ -43,20 +57,36 @@ pub struct ParsableScreenshotData {
 pub struct ParsableDataCombined {
     title: String,
     app_id: String,
+    shortcut_app_id: u32,
     path_box_art: Option<PathBuf>,
     path_icon: Option<PathBuf>,
+    path_game_dir: Option<PathBuf>,
+    metadata: GameMetadata,
 }
 impl ParsableDataCombined {
     fn combine(
-        path_box_art_dir: &Path,
+        steam_id: u64,
+        path_user_dir: &Path,
+        path_box_art_dir: Option<&Path>,
         shortcut_data: ParsableShortcutData,
-        screenshot_data: ParsableScreenshotData,
+        screenshot_data: Option<ParsableScreenshotData>,
     ) -> Self {
+        // Prefer the ID Steam recorded, in case it differs from the computed one
+        let (app_id, shortcut_app_id) =
+            match screenshot_data.and_then(|d| d.app_id.parse::<u64>().ok()) {
+                Some(game_id) => (game_id, (game_id >> 32) as u32),
+                None => (
+                    get_shortcut_game_id(shortcut_data.app_id),
+                    shortcut_data.app_id,
+                ),
+            };
+
         // Regular Steam shortcut images have an extra "p" at the end of the image file names,
         // whereas the flathub Steam ones don't.
-        let path_box_art =
-            get_existing_image_path(path_box_art_dir, format!("{}p", shortcut_data.box_art_id))
-                .or_else(|| get_existing_image_path(path_box_art_dir, &shortcut_data.box_art_id));
+        let path_box_art = path_box_art_dir.and_then(|path_box_art_dir| {
+            get_existing_image_path(path_box_art_dir, format!("{shortcut_app_id}p"))
+                .or_else(|| get_existing_image_path(path_box_art_dir, shortcut_app_id.to_string()))
+        });
 
         let mut path_icon = None;
         if !shortcut_data.icon.is_empty() {
@@ -66,51 +96,102 @@ impl ParsableDataCombined {
             }
         }
 
+        let path_executable = unquote_path(&shortcut_data.exe);
+        let path_working_dir = unquote_path(&shortcut_data.start_dir);
+        // The starting directory is usually the one containing the game. Relative paths, such as
+        // the `./` Steam sets for shortcuts to commands, don't point to anything useful.
+        let path_game_dir = path_working_dir
+            .clone()
+            .filter(|p| p.is_absolute())
+            .and_then(some_if_dir);
+
+        let metadata = GameMetadata {
+            path_executable,
+            path_working_dir,
+            launch_options: Some(shortcut_data.launch_options).filter(|o| !o.is_empty()),
+            is_favorite: shortcut_data.tags.iter().any(|t| t == "favorite"),
+            collections: shortcut_data
+                .tags
+                .into_iter()
+                .filter(|t| t != "favorite")
+                .collect(),
+            is_hidden: shortcut_data.is_hidden,
+            is_vr: shortcut_data.is_vr,
+            last_played: Some(u64::from(shortcut_data.last_play_time)).filter(|t| *t > 0),
+            screenshots: get_screenshots(path_user_dir, shortcut_app_id),
+            steam_id: Some(steam_id),
+            ..Default::default()
+        };
+
         ParsableDataCombined {
             title: shortcut_data.title,
-            app_id: screenshot_data.app_id,
+            app_id: app_id.to_string(),
+            shortcut_app_id,
             path_box_art,
             path_icon,
+            path_game_dir,
+            metadata,
         }
     }
 }
 
-/// Paths to the files required for parsing all Steam shortcut data
+/// Paths to the files used for parsing all Steam shortcut data of an account. Only
+/// `shortcuts.vdf` is required.
 #[derive(Debug)]
 pub struct UserDataFiles {
+    steam_id: u64,
+    path_user_dir: PathBuf,
     path_shortcuts: PathBuf,
-    path_screenshots: PathBuf,
-    path_box_art_dir: PathBuf,
+    path_screenshots: Option<PathBuf>,
+    path_box_art_dir: Option<PathBuf>,
 }
 
 const LAUNCHER: SupportedLaunchers = SupportedLaunchers::SteamShortcuts;
 
 // UTILS -----------------------------------------------------------------------------------------
-#[tracing::instrument(level = "trace")]
-fn find_userdata_files(
-    path_steam_userdata_dir: &Path,
-) -> Result<impl Iterator<Item = UserDataFiles>, GamesParsingError> {
-    Ok(read_dir(path_steam_userdata_dir)?
-        .flatten()
-        .filter_map(|p| {
-            if !p.file_type().is_ok_and(|f| f.is_dir()) {
-                return None;
-            }
+/// Get the ID Steam uses to launch a shortcut, i.e. with `steam://rungameid/<id>`, from its
+/// 32-bit app ID
+fn get_shortcut_game_id(app_id: u32) -> u64 {
+    (u64::from(app_id) << 32) | 0x0200_0000
+}
+
+/// Get an integer field of a `shortcuts.vdf` entry, which is `0` if missing. Integers are stored
+/// as signed, even though app IDs use the full 32 bits.
+pub(super) fn get_shortcut_u32(shortcut: &KeyValues, key: &str) -> u32 {
+    shortcut.get_parsed::<i32>(key).map_or(0, |n| n as u32)
+}
+
+/// Get the app ID of a `shortcuts.vdf` entry, and whether it had to be computed, as older
+/// entries don't include it
+pub(super) fn get_shortcut_app_id(shortcut: &KeyValues) -> (u32, bool) {
+    match get_shortcut_u32(shortcut, "appid") {
+        0 => (
+            calculate_app_id(
+                shortcut.get_str("Exe").unwrap_or_default(),
+                shortcut.get_str("AppName").unwrap_or_default(),
+            ),
+            true,
+        ),
+        app_id => (app_id, false),
+    }
+}
+
+/// Get a path from a `shortcuts.vdf` field, which Steam wraps in quotes
+fn unquote_path(path: &str) -> Option<PathBuf> {
+    let path = path.trim().trim_matches('"');
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
 
-            let p = p.path();
+#[tracing::instrument(level = "trace")]
+fn find_userdata_files(userdata_dirs: Vec<(u64, PathBuf)>) -> impl Iterator<Item = UserDataFiles> {
+    userdata_dirs
+        .into_iter()
+        .filter_map(|(steam_id, p)| {
             let path_config = p.join("config");
             if !path_config.is_dir() {
                 return None;
             }
 
-            let path_screenshots = p.join("760").join("screenshots.vdf");
-            if !path_screenshots.is_file() {
-                trace!(
-                    "{LAUNCHER} - Couldn't find Steam user screenshots file at {path_screenshots:?}"
-                );
-                return None;
-            }
-
             let path_shortcuts = path_config.join("shortcuts.vdf");
             if !path_shortcuts.is_file() {
                 trace!(
@@ -119,36 +200,65 @@ fn find_userdata_files(
                 return None;
             }
 
-            let path_box_art_dir = path_config.join("grid");
-            if !path_box_art_dir.is_dir() {
+            let path_screenshots = some_if_file(p.join("760").join("screenshots.vdf"));
+            if path_screenshots.is_none() {
+                trace!("{LAUNCHER} - Couldn't find Steam user screenshots file in {p:?}");
+            }
+
+            let path_box_art_dir = some_if_dir(path_config.join("grid"));
+            if path_box_art_dir.is_none() {
                 trace!(
-                    "{LAUNCHER} - Couldn't find Steam user shortcuts box art directory at {path_shortcuts:?}"
+                    "{LAUNCHER} - Couldn't find Steam user shortcuts box art directory in {path_config:?}"
                 );
-                return None;
             }
 
             Some(UserDataFiles {
+                steam_id,
+                path_user_dir: p,
                 path_shortcuts,
                 path_screenshots,
                 path_box_art_dir,
             })
         })
-        )
 }
 
 #[tracing::instrument(level = "trace")]
 fn get_parsable_shortcuts_data(
     path_shortcuts: &Path,
 ) -> Result<Vec<ParsableShortcutData>, GamesParsingError> {
-    let content = read(path_shortcuts)?;
-    let shortcuts = parse_shortcuts(content.as_slice()).map_err(GamesParsingError::Other)?;
+    let shortcuts_vdf = vdf::parse_binary(&read(path_shortcuts)?)?;
+
+    let Some(shortcuts) = shortcuts_vdf.get_object("shortcuts") else {
+        warn!("{LAUNCHER} - No `shortcuts` block found in {path_shortcuts:?}");
+        return Ok(vec![]);
+    };
 
     Ok(shortcuts
-        .into_iter()
-        .map(|s| ParsableShortcutData {
-            title: s.app_name.to_owned(),
-            box_art_id: s.app_id.to_string(),
-            icon: s.icon.to_owned(),
+        .iter()
+        .filter_map(|(_, shortcut)| shortcut.as_object())
+        .map(|s| {
+            let get_str = |key: &str| s.get_str(key).unwrap_or_default().to_owned();
+            let get_u32 = |key: &str| get_shortcut_u32(s, key);
+            let (app_id, is_app_id_computed) = get_shortcut_app_id(s);
+
+            ParsableShortcutData {
+                icon: get_str("icon"),
+                app_id,
+                is_app_id_computed,
+                title: get_str("AppName"),
+                exe: get_str("Exe"),
+                start_dir: get_str("StartDir"),
+                launch_options: get_str("LaunchOptions"),
+                is_hidden: get_u32("IsHidden") != 0,
+                is_vr: get_u32("OpenVR") != 0,
+                tags: s
+                    .get_object("tags")
+                    .into_iter()
+                    .flat_map(KeyValues::iter)
+                    .filter_map(|(_, tag)| tag.as_str().map(str::to_owned))
+                    .collect(),
+                last_play_time: get_u32("LastPlayTime"),
+            }
         })
         .collect())
 }
@@ -157,77 +267,63 @@ fn get_parsable_shortcuts_data(
 fn get_parsable_screenshots_data(
     path_screenshots: &Path,
 ) -> Result<Vec<ParsableScreenshotData>, GamesParsingError> {
-    let file_content = &read_to_string(path_screenshots)?;
-
-    Ok(parse_screenshots_vdf(file_content, path_screenshots)?.1)
-}
-
-#[tracing::instrument(level = "trace", skip(file_content))]
-fn parse_screenshots_vdf<'a>(
-    file_content: &'a str,
-    file_path: &Path,
-) -> IResult<&'a str, Vec<ParsableScreenshotData>> {
-    let mut data = vec![];
-
-    // Parse until "shortcutnames" and grab the next block contained by `{}`
-    let (file_content, _) = take_until("\"shortcutnames\"").parse(file_content)?;
-    let (file_content, _) = take_till(|c| c == '{').parse(file_content)?;
-    let (file_content, mut block) =
-        delimited(char('{'), take_till(|c| c == '}'), char('}')).parse(file_content)?;
-
-    // Remove trailing whitespace so the below while block condition fails before running with the empty line
-    block = block.trim_end();
-
-    while let Ok((file_content, _)) = parse_not_double_quote(block) {
-        // APP ID
-        let (file_content, app_id) = parse_between_double_quotes(file_content)?;
-
-        let (file_content, _) = parse_not_double_quote(file_content)?;
-
-        // TITLE
-        let (file_content, title) = parse_between_double_quotes(file_content)?;
-
-        data.push(ParsableScreenshotData {
-            title: title.to_owned(),
-            app_id: app_id.to_owned(),
-        });
-
-        block = file_content;
-    }
-
-    Ok((file_content, data))
+    let screenshots_vdf = vdf::parse_file(path_screenshots)?;
+
+    let Some(shortcut_names) = screenshots_vdf.get_object_at(&["screenshots", "shortcutnames"])
+    else {
+        warn!("{LAUNCHER} - No `shortcutnames` block found in {path_screenshots:?}");
+        return Ok(vec![]);
+    };
+
+    Ok(shortcut_names
+        .iter()
+        .filter_map(|(app_id, title)| {
+            Some(ParsableScreenshotData {
+                title: title.as_str()?.to_owned(),
+                app_id: app_id.to_owned(),
+            })
+        })
+        .collect())
 }
 
 // STEAM SHORTCUTS / NON-STEAM GAMES ---------------------------------------------------------------
 #[derive(Debug)]
 pub struct SteamShortcuts {
+    path_steam_dir: PathBuf,
     path_steam_userdata_dir: PathBuf,
-    is_using_flatpak: bool,
+    install_variant: InstallVariant,
+    steam_accounts: SteamAccountFilter,
+    include_hidden: bool,
 }
 
 impl SteamShortcuts {
-    pub fn new(path_home: &Path, path_data: &Path) -> Self {
-        let mut path_steam_userdata_dir = get_steam_dir(path_home, path_data).join("userdata");
-        let mut is_using_flatpak = false;
-
-        if !path_steam_userdata_dir.is_dir() {
-            debug_fallback_flatpak!();
-
-            is_using_flatpak = true;
-            path_steam_userdata_dir = get_steam_flatpak_dir(path_home).join("userdata");
-        };
+    pub fn new(
+        steam_dir: SteamDir,
+        steam_accounts: SteamAccountFilter,
+        include_hidden: bool,
+    ) -> Self {
+        let SteamDir {
+            path: path_steam_dir,
+            install_variant,
+            ..
+        } = steam_dir;
+        let path_steam_userdata_dir = path_steam_dir.join("userdata");
 
         debug_path!("userdata dir path", path_steam_userdata_dir);
 
         Self {
+            path_steam_dir,
             path_steam_userdata_dir,
-            is_using_flatpak,
+            install_variant,
+            steam_accounts,
+            include_hidden,
         }
     }
 
     #[tracing::instrument(level = "trace")]
     fn parse_combined_data(&self) -> Result<Option<Vec<ParsableDataCombined>>, GamesParsingError> {
-        let mut userdata_files = find_userdata_files(&self.path_steam_userdata_dir)?.peekable();
+        let userdata_dirs = get_userdata_dirs(&self.path_steam_dir, &self.steam_accounts);
+        let mut userdata_files = find_userdata_files(userdata_dirs).peekable();
         if userdata_files.peek().is_none() {
             return Ok(None);
         };
@@ -235,30 +331,50 @@ impl SteamShortcuts {
         userdata_files
             .map(
                 |UserDataFiles {
+                     steam_id,
+                     path_user_dir,
                      path_shortcuts,
                      path_screenshots,
                      path_box_art_dir,
                  }| {
                     let shortcuts_data = get_parsable_shortcuts_data(&path_shortcuts)?;
-                    let mut screenshots_data = get_parsable_screenshots_data(&path_screenshots)?;
+                    let mut screenshots_data = path_screenshots
+                        .map(|p| get_parsable_screenshots_data(&p))
+                        .transpose()
+                        .inspect_err(|e| {
+                            warn!("{LAUNCHER} - Error with parsing screenshots.vdf: {e}")
+                        })
+                        .unwrap_or_default()
+                        .unwrap_or_default();
 
                     let res = shortcuts_data
                         .into_iter()
-                        .filter_map(|shortcut_data| {
-                            screenshots_data
-                                .iter_mut()
-                                // Reverse because the last entry is the newest one and this file doesn't seem to
-                                // get reset, so we want to take the one most likely to be correct (in the
-                                // case of duplicate entries)
-                                .rev()
-                                .find(|d| !d.title.is_empty() && d.title == shortcut_data.title)
-                                .map(|screenshot_data| {
-                                    ParsableDataCombined::combine(
-                                        &path_box_art_dir,
-                                        shortcut_data,
-                                        mem::take(screenshot_data),
-                                    )
+                        .map(|shortcut_data| {
+                            // Only needed for older entries without an app ID, since the computed
+                            // ID may not match the one used by Steam
+                            let screenshot_data = shortcut_data
+                                .is_app_id_computed
+                                .then(|| {
+                                    screenshots_data
+                                        .iter_mut()
+                                        // Reverse because the last entry is the newest one and this file doesn't seem to
+                                        // get reset, so we want to take the one most likely to be correct (in the
+                                        // case of duplicate entries)
+                                        .rev()
+                                        .find(|d| {
+                                            !d.title.is_empty() && d.title == shortcut_data.title
+                                        })
+                                        .map(mem::take)
                                 })
+                                .flatten();
+
+                            ParsableDataCombined::combine(
+                                steam_id,
+                                &path_user_dir,
+                                path_box_art_dir.as_deref(),
+                                shortcut_data,
+                                screenshot_data,
+                            )
                         })
                         .collect_vec();
 
@@ -276,6 +392,10 @@ impl Launcher for SteamShortcuts {
         LAUNCHER
     }
 
+    fn get_install_variant(&self) -> InstallVariant {
+        self.install_variant.clone()
+    }
+
     fn is_detected(&self) -> bool {
         self.path_steam_userdata_dir.is_dir()
     }
@@ -294,23 +414,38 @@ impl Launcher for SteamShortcuts {
             warn_no_games!();
         }
 
+        // Official Proton versions are installed in libraries like any other app
+        let paths_libraries = get_library_folders(&self.path_steam_dir)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|l| l.exists)
+            .map(|l| l.path)
+            .collect::<Vec<_>>();
+        let compat_tools = CompatTools::new(&self.path_steam_dir, &paths_libraries);
+
         Ok(shortcut_data
             .into_iter()
+            .filter(|d| self.include_hidden || !d.metadata.is_hidden)
             .map(
                 |ParsableDataCombined {
                      app_id,
+                     shortcut_app_id,
                      title,
                      path_box_art,
                      path_icon,
+                     path_game_dir,
+                     metadata,
                  }| {
-                    let launch_command = get_steam_launch_command(app_id, self.is_using_flatpak);
-                    let path_game_dir = None;
+                    let launch_command = get_steam_launch_command(app_id, &self.install_variant);
                     let title = clean_game_title(title);
 
                     trace!("{LAUNCHER} - Game directory for '{title}': {path_game_dir:?}");
                     trace!("{LAUNCHER} - Box art for '{title}': {path_box_art:?}");
                     trace!("{LAUNCHER} - Icon for '{title}': {path_icon:?}");
 
+                    let compat_tool = compat_tools
+                        .get_compat_tool_shortcut(shortcut_app_id, &self.path_steam_dir);
+
                     Game {
                         title: clean_game_title(&title),
                         launch_command,
@@ -318,6 +453,11 @@ impl Launcher for SteamShortcuts {
                         path_game_dir,
                         path_icon,
                         source: LAUNCHER.clone(),
+                        install_variant: self.install_variant.clone(),
+                        metadata: GameMetadata {
+                            compat_tool,
+                            ..metadata
+                        },
                     }
                 },
             )
@@ -332,22 +472,26 @@ mod tests {
     use super::*;
     use crate::linux::test_utils::get_mock_file_system_path;
 
-    #[test_case(false, "", ".local/share"; "standard")]
-    #[test_case(false, "steam_symlinks", ""; "standard - fallback steam root")]
-    #[test_case(true, "", "invalid/data/path"; "flatpak")]
+    #[test_case(InstallVariant::Native, ".local/share/Steam"; "standard")]
+    #[test_case(InstallVariant::Native, "steam_symlinks/.steam/debian-installation"; "debian")]
+    #[test_case(InstallVariant::Flatpak, ".var/app/com.valvesoftware.Steam/data/Steam"; "flatpak")]
+    #[test_case(InstallVariant::Snap, "snap_home/snap/steam/common/.local/share/Steam"; "snap")]
     fn test_steam_shortcuts_launcher(
-        is_testing_flatpak: bool,
-        path_home: &str,
-        path_data: &str,
+        install_variant: InstallVariant,
+        path_steam_dir: &str,
     ) -> Result<(), GamesParsingError> {
         let path_files_system_mock = get_mock_file_system_path();
         let launcher = SteamShortcuts::new(
-            &path_files_system_mock.join(path_home),
-            &path_files_system_mock.join(path_data),
+            SteamDir::new(
+                path_files_system_mock.join(path_steam_dir),
+                install_variant.clone(),
+            ),
+            SteamAccountFilter::All,
+            true,
         );
 
         assert!(launcher.is_detected());
-        assert!(launcher.is_using_flatpak == is_testing_flatpak);
+        assert_eq!(launcher.get_install_variant(), install_variant);
 
         let games = launcher.get_detected_games()?;
         assert_eq!(games.len(), 3);
@@ -370,6 +514,144 @@ mod tests {
         assert!(games[1].path_icon.is_none());
         assert!(games[2].path_icon.is_none());
 
+        // Launch IDs are derived from the app IDs in `shortcuts.vdf`, matching `screenshots.vdf`
+        assert!(
+            games[0]
+                .launch_command
+                .get_args()
+                .any(|a| a == "steam://rungameid/11957248871186300928")
+        );
+
+        // Only the mock for the standard install selects a compatibility tool for a shortcut
+        if path_steam_dir == ".local/share/Steam" {
+            assert!(
+                games[0]
+                    .metadata
+                    .compat_tool
+                    .as_ref()
+                    .is_some_and(|t| t.name == "GE-Proton9-20" && t.path_prefix.is_some())
+            );
+        }
+        assert!(games[1].metadata.compat_tool.is_none());
+        assert!(games[2].metadata.compat_tool.is_none());
+
+        // Screenshots are found using the shortcut's app ID
+        if path_steam_dir == ".local/share/Steam" {
+            assert!(games[0].metadata.screenshots.first().is_some_and(|s| {
+                s.path
+                    .ends_with("760/remote/2784013951/screenshots/20240202080000_1.jpg")
+                    && s.path_thumbnail.is_some()
+            }));
+        }
+        assert!(games[1].metadata.screenshots.is_empty());
+
         Ok(())
     }
+
+    #[test]
+    fn test_steam_shortcuts_without_screenshots() -> Result<(), GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let launcher = SteamShortcuts::new(
+            SteamDir::new(
+                path_file_system_mock.join("steam_shortcuts_only/Steam"),
+                InstallVariant::Native,
+            ),
+            SteamAccountFilter::All,
+            true,
+        );
+
+        assert!(launcher.is_detected());
+
+        // Neither `screenshots.vdf` nor a `grid` directory exist for this user
+        let games = launcher.get_detected_games()?;
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].title, "Old Shortcut");
+        assert_eq!(games[1].title, "New Shortcut");
+
+        let old_app_id = calculate_app_id(r#""/usr/bin/old-game""#, "Old Shortcut");
+        let expected_args = [
+            format!("steam://rungameid/{}", get_shortcut_game_id(old_app_id)),
+            format!("steam://rungameid/{}", get_shortcut_game_id(3000000001)),
+        ];
+        for (game, expected_arg) in games.iter().zip(expected_args) {
+            assert!(game.launch_command.get_args().any(|a| *a == *expected_arg));
+            assert!(game.path_box_art.is_none());
+        }
+
+        // Older entries are missing most optional fields
+        let metadata = &games[0].metadata;
+        assert_eq!(
+            metadata.path_executable,
+            Some(PathBuf::from("/usr/bin/old-game"))
+        );
+        assert_eq!(
+            metadata.path_working_dir,
+            Some(PathBuf::from("/not/a/valid/dir/"))
+        );
+        assert!(games[0].path_game_dir.is_none());
+        assert!(metadata.launch_options.is_none());
+        assert!(metadata.collections.is_empty());
+        assert!(!metadata.is_favorite);
+        assert!(!metadata.is_hidden);
+        assert!(!metadata.is_vr);
+        assert!(metadata.last_played.is_none());
+
+        let metadata = &games[1].metadata;
+        assert_eq!(
+            metadata.path_executable,
+            Some(PathBuf::from("/usr/bin/new-game"))
+        );
+        assert_eq!(games[1].path_game_dir, Some(PathBuf::from("/usr/bin/")));
+        assert_eq!(metadata.launch_options.as_deref(), Some("--fullscreen"));
+        assert_eq!(metadata.collections, ["Indie"]);
+        assert!(metadata.is_favorite);
+        assert!(metadata.is_hidden);
+        assert!(metadata.is_vr);
+        assert_eq!(metadata.last_played, Some(1700000000));
+
+        // Hidden shortcuts can be excluded
+        let launcher = SteamShortcuts::new(
+            SteamDir::new(
+                path_file_system_mock.join("steam_shortcuts_only/Steam"),
+                InstallVariant::Native,
+            ),
+            SteamAccountFilter::All,
+            false,
+        );
+        let games = launcher.get_detected_games()?;
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].title, "Old Shortcut");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_combine_screenshot_fallback() {
+        let shortcut_data = ParsableShortcutData {
+            icon: String::new(),
+            app_id: 1,
+            is_app_id_computed: true,
+            title: String::from("Game"),
+            ..Default::default()
+        };
+        let screenshot_data = ParsableScreenshotData {
+            title: String::from("Game"),
+            app_id: get_shortcut_game_id(2).to_string(),
+        };
+
+        let combined = ParsableDataCombined::combine(
+            1,
+            Path::new(""),
+            None,
+            shortcut_data.clone(),
+            Some(screenshot_data),
+        );
+        assert_eq!(combined.app_id, get_shortcut_game_id(2).to_string());
+        assert_eq!(combined.shortcut_app_id, 2);
+
+        let combined = ParsableDataCombined::combine(1, Path::new(""), None, shortcut_data, None);
+        assert_eq!(combined.app_id, get_shortcut_game_id(1).to_string());
+        assert_eq!(combined.shortcut_app_id, 1);
+    }
 }
@@ -0,0 +1,703 @@
+// PATHS:
+// - <steam dir>/config/loginusers.vdf
+// - <steam dir>/userdata/<account id>/config/localconfig.vdf
+// - <steam dir>/userdata/<account id>/config/grid/
+// - <steam dir>/userdata/<account id>/config/cloudstorage/cloud-storage-namespace-1.json
+// - <steam dir>/userdata/<account id>/7/remote/sharedconfig.vdf
+// - <steam dir>/userdata/<account id>/760/remote/<app id>/screenshots/
+// - <steam dir>/userdata/<account id>/config/librarycache/achievement_progress.json
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use tracing::{debug, error, trace, warn};
+
+use crate::{
+    data::{Achievements, Screenshot, SteamAccountFilter, SupportedLaunchers},
+    error::GamesParsingError,
+    json,
+    macros::logs::debug_path,
+    utils::{get_existing_image_path, some_if_file},
+    vdf::{self, KeyValues},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Steam;
+/// SteamID of the individual account with account ID `0`, from which all others are offset
+const STEAM_ID_BASE: u64 = 0x0110_0001_0000_0000;
+
+/// A Steam account which has logged in on this system, as listed in `config/loginusers.vdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SteamUser {
+    /// 64-bit SteamID of the account.
+    pub steam_id: u64,
+    /// Name used to log in to the account.
+    pub account_name: String,
+    /// Name shown to other users.
+    pub persona_name: String,
+    /// Whether this is the account which logged in most recently, i.e. the active one.
+    pub is_most_recent: bool,
+    /// When the account last logged in, as a Unix timestamp in seconds.
+    pub last_login: Option<u64>,
+}
+
+impl SteamUser {
+    /// Get the 32-bit account ID, which is the name of the account's directory in `userdata`.
+    #[must_use]
+    pub fn get_account_id(&self) -> u32 {
+        get_account_id(self.steam_id)
+    }
+}
+
+/// Get the 32-bit account ID of the given SteamID
+fn get_account_id(steam_id: u64) -> u32 {
+    (steam_id & 0xFFFF_FFFF) as u32
+}
+
+/// Get all accounts listed in `loginusers.vdf`, starting with the one which logged in most
+/// recently
+#[tracing::instrument(level = "trace")]
+pub fn get_users(path_steam_dir: &Path) -> Result<Vec<SteamUser>, GamesParsingError> {
+    let path_login_users = path_steam_dir.join("config/loginusers.vdf");
+
+    debug_path!("loginusers.vdf", path_login_users);
+
+    let login_users = vdf::parse_file(&path_login_users)?;
+    let Some(users) = login_users.get_object("users") else {
+        warn!("{LAUNCHER} - No `users` block found in {path_login_users:?}");
+        return Ok(vec![]);
+    };
+
+    let mut users = users
+        .iter()
+        .filter_map(|(steam_id, user)| {
+            let user = user.as_object()?;
+            let get_str = |key: &str| user.get_str(key).unwrap_or_default().to_owned();
+
+            Some(SteamUser {
+                steam_id: steam_id.parse().ok()?,
+                account_name: get_str("AccountName"),
+                persona_name: get_str("PersonaName"),
+                is_most_recent: user.get_str("MostRecent") == Some("1"),
+                last_login: user.get_parsed("Timestamp"),
+            })
+        })
+        .collect::<Vec<_>>();
+    users.sort_by_key(|u| (!u.is_most_recent, std::cmp::Reverse(u.last_login)));
+
+    Ok(users)
+}
+
+/// Get the SteamIDs and `userdata` directories of all accounts matching the given filter, starting
+/// with the account which logged in most recently
+#[tracing::instrument(level = "trace")]
+pub(super) fn get_userdata_dirs(
+    path_steam_dir: &Path,
+    filter: &SteamAccountFilter,
+) -> Vec<(u64, PathBuf)> {
+    let Ok(entries) = read_dir(path_steam_dir.join("userdata")) else {
+        debug!("{LAUNCHER} - No userdata directory found in {path_steam_dir:?}");
+        return vec![];
+    };
+
+    let users = get_users(path_steam_dir)
+        .inspect_err(|e| debug!("{LAUNCHER} - Could not get users from loginusers.vdf: {e}"))
+        .unwrap_or_default();
+
+    let mut userdata_dirs = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| {
+            let account_id = e.file_name().to_str()?.parse::<u32>().ok()?;
+            // Data of the anonymous user, i.e. when no one is logged in
+            if account_id == 0 {
+                return None;
+            }
+
+            Some((STEAM_ID_BASE + u64::from(account_id), e.path()))
+        })
+        .collect::<Vec<_>>();
+
+    // Known users first, in the same order as `loginusers.vdf`, then any others by ID
+    userdata_dirs.sort_by_key(|(steam_id, _)| {
+        (
+            users
+                .iter()
+                .position(|u| u.steam_id == *steam_id)
+                .unwrap_or(usize::MAX),
+            *steam_id,
+        )
+    });
+
+    match filter {
+        SteamAccountFilter::All => {}
+        SteamAccountFilter::MostRecent => match users.first() {
+            Some(user) => userdata_dirs.retain(|(steam_id, _)| *steam_id == user.steam_id),
+            None => warn!("{LAUNCHER} - Could not find the most recent user, so using all users"),
+        },
+        SteamAccountFilter::SteamId(id) => {
+            // Either form of the ID is accepted, as both are commonly used
+            userdata_dirs.retain(|(steam_id, _)| {
+                steam_id == id || get_account_id(*steam_id) == get_account_id(*id)
+            });
+        }
+    }
+
+    trace!("{LAUNCHER} - userdata directories: {userdata_dirs:?}");
+
+    userdata_dirs
+}
+
+/// Data of a single app for one account
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserAppData {
+    /// SteamID of the account
+    pub steam_id: u64,
+    /// When the account last played the app, as a Unix timestamp in seconds
+    pub last_played: Option<u64>,
+    /// Total time the account played the app for, in minutes
+    pub playtime: Option<u64>,
+    /// Launch options the account set for the app, e.g. `gamemoderun %command%`
+    pub launch_options: Option<String>,
+    /// Custom box art the account set for the app
+    pub path_box_art: Option<PathBuf>,
+    /// Names of the collections the account added the app to
+    pub collections: Vec<String>,
+    /// Whether the account added the app to its favourites
+    pub is_favorite: bool,
+    /// Whether the account hid the app
+    pub is_hidden: bool,
+    /// Screenshots the account took of the app
+    pub screenshots: Vec<Screenshot>,
+    /// How many of the app's achievements the account unlocked
+    pub achievements: Option<Achievements>,
+}
+
+/// Per-user data of each account matching a filter, e.g. playtime and custom art
+#[derive(Debug, Default)]
+pub struct SteamUsers {
+    users: Vec<UserData>,
+}
+
+#[derive(Debug)]
+struct UserData {
+    steam_id: u64,
+    path_user_dir: PathBuf,
+    apps: HashMap<u32, LocalConfigApp>,
+    collections: Collections,
+    achievements: HashMap<u32, Achievements>,
+}
+
+/// Data of a single app from an account's `localconfig.vdf`
+#[derive(Debug, Clone, Default)]
+struct LocalConfigApp {
+    last_played: Option<u64>,
+    playtime: Option<u64>,
+    launch_options: Option<String>,
+}
+
+/// Collections an account has organised its apps into
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Collections {
+    /// Names of the user-created collections each app is in
+    names: HashMap<u32, Vec<String>>,
+    favorites: HashSet<u32>,
+    hidden: HashSet<u32>,
+}
+
+impl Collections {
+    /// Add the apps to the collection with the given ID, where `favorite` and `hidden` are
+    /// special collections created by Steam
+    fn add(&mut self, id: &str, name: Option<&str>, app_ids: impl IntoIterator<Item = u32>) {
+        match (id, name) {
+            ("favorite", _) => self.favorites.extend(app_ids),
+            ("hidden", _) => self.hidden.extend(app_ids),
+            (_, Some(name)) => {
+                for app_id in app_ids {
+                    self.names.entry(app_id).or_default().push(name.to_owned());
+                }
+            }
+            // Dynamic collections have no name of their own, and no apps are added to them
+            (_, None) => {}
+        }
+    }
+}
+
+impl SteamUsers {
+    /// Read the per-user data of all accounts of the given Steam directory matching the filter
+    #[tracing::instrument(level = "trace")]
+    pub fn new(path_steam_dir: &Path, filter: &SteamAccountFilter) -> Self {
+        let users = get_userdata_dirs(path_steam_dir, filter)
+            .into_iter()
+            .map(|(steam_id, path_user_dir)| {
+                let path_config = path_user_dir.join("config");
+                UserData {
+                    steam_id,
+                    apps: get_apps_user_data(&path_config.join("localconfig.vdf")),
+                    collections: get_collections(&path_user_dir),
+                    achievements: get_achievements(
+                        &path_config.join("librarycache/achievement_progress.json"),
+                    ),
+                    path_user_dir,
+                }
+            })
+            .collect();
+
+        Self { users }
+    }
+
+    /// Get the data of the given app for the account which played it most recently, or the first
+    /// account with any data for it (e.g. custom art) if none have played it.
+    #[must_use]
+    pub fn get_app_data(&self, app_id: u32) -> Option<UserAppData> {
+        self.users
+            .iter()
+            .filter_map(|user| {
+                let LocalConfigApp {
+                    last_played,
+                    playtime,
+                    launch_options,
+                } = user.apps.get(&app_id).cloned().unwrap_or_default();
+                let data = UserAppData {
+                    steam_id: user.steam_id,
+                    last_played,
+                    playtime,
+                    launch_options,
+                    path_box_art: get_existing_image_path(
+                        &user.path_user_dir.join("config/grid"),
+                        format!("{app_id}p"),
+                    ),
+                    collections: user
+                        .collections
+                        .names
+                        .get(&app_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    is_favorite: user.collections.favorites.contains(&app_id),
+                    is_hidden: user.collections.hidden.contains(&app_id),
+                    screenshots: get_screenshots(&user.path_user_dir, app_id),
+                    achievements: user.achievements.get(&app_id).cloned(),
+                };
+
+                let is_empty = data
+                    == UserAppData {
+                        steam_id: user.steam_id,
+                        ..Default::default()
+                    };
+                (!is_empty).then_some(data)
+            })
+            .reduce(|best, data| {
+                if data.last_played > best.last_played {
+                    data
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+/// Get the screenshots an account took of the given app (or shortcut), each with its thumbnail if
+/// one exists
+#[tracing::instrument(level = "trace")]
+pub(super) fn get_screenshots(path_user_dir: &Path, app_id: u32) -> Vec<Screenshot> {
+    let path_screenshots_dir = path_user_dir
+        .join("760/remote")
+        .join(app_id.to_string())
+        .join("screenshots");
+    let Ok(entries) = read_dir(&path_screenshots_dir) else {
+        return vec![];
+    };
+
+    // File names start with the date and time they were taken, e.g. `20240101120000_1.jpg`
+    let mut paths = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .map(|e| e.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| Screenshot {
+            path_thumbnail: path
+                .file_name()
+                .and_then(|name| some_if_file(path_screenshots_dir.join("thumbnails").join(name))),
+            path,
+        })
+        .collect()
+}
+
+/// Get the achievement progress of each app from a user's `achievement_progress.json`, which
+/// lists each app as an `[app id, progress]` pair
+#[tracing::instrument(level = "trace")]
+fn get_achievements(path_achievement_progress: &Path) -> HashMap<u32, Achievements> {
+    if !path_achievement_progress.is_file() {
+        return HashMap::new();
+    }
+
+    let Ok(achievement_progress) = json::parse_file(path_achievement_progress)
+        .inspect_err(|e| error!("{LAUNCHER} - Error with parsing achievement_progress.json:\n{e}"))
+    else {
+        return HashMap::new();
+    };
+
+    achievement_progress
+        .get_array("mapCache")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| {
+            let [app_id, progress] = entry.as_array()? else {
+                return None;
+            };
+            let get_u32 = |key: &str| u32::try_from(progress.get_u64(key)?).ok();
+
+            Some((
+                u32::try_from(app_id.as_u64()?).ok()?,
+                Achievements {
+                    unlocked: get_u32("unlocked")?,
+                    total: get_u32("total").filter(|t| *t > 0)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Get the last played time, playtime and launch options of each app from a user's
+/// `localconfig.vdf`
+#[tracing::instrument(level = "trace")]
+fn get_apps_user_data(path_local_config: &Path) -> HashMap<u32, LocalConfigApp> {
+    if !path_local_config.is_file() {
+        return HashMap::new();
+    }
+
+    let Ok(local_config) = vdf::parse_file(path_local_config)
+        .inspect_err(|e| error!("{LAUNCHER} - Error with parsing localconfig.vdf:\n{e}"))
+    else {
+        return HashMap::new();
+    };
+
+    local_config
+        .get_object_at(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"])
+        .into_iter()
+        .flat_map(KeyValues::iter)
+        .filter_map(|(app_id, app)| {
+            let app = app.as_object()?;
+            Some((
+                app_id.parse().ok()?,
+                LocalConfigApp {
+                    last_played: app.get_parsed::<u64>("LastPlayed").filter(|t| *t > 0),
+                    playtime: app.get_parsed::<u64>("Playtime"),
+                    launch_options: app
+                        .get_str("LaunchOptions")
+                        .filter(|o| !o.trim().is_empty())
+                        .map(str::to_owned),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Get the collections of the account with the given `userdata` directory, from its cloud storage
+/// or, for older versions of Steam, the tags in `sharedconfig.vdf`
+#[tracing::instrument(level = "trace")]
+fn get_collections(path_user_dir: &Path) -> Collections {
+    let path_cloud_storage =
+        path_user_dir.join("config/cloudstorage/cloud-storage-namespace-1.json");
+    let path_shared_config = path_user_dir.join("7/remote/sharedconfig.vdf");
+
+    let collections = if path_cloud_storage.is_file() {
+        debug_path!("cloud storage collections", path_cloud_storage);
+        parse_cloud_storage_collections(&path_cloud_storage)
+    } else if path_shared_config.is_file() {
+        debug_path!("sharedconfig.vdf", path_shared_config);
+        parse_shared_config_collections(&path_shared_config)
+    } else {
+        return Collections::default();
+    };
+
+    collections
+        .inspect_err(|e| error!("{LAUNCHER} - Error with parsing collections:\n{e}"))
+        .unwrap_or_default()
+}
+
+/// Parse collections from the cloud storage file, which lists each collection as a
+/// `[key, entry]` pair, with the collection itself stored as JSON in the entry's `value`
+fn parse_cloud_storage_collections(
+    path_cloud_storage: &Path,
+) -> Result<Collections, GamesParsingError> {
+    let mut collections = Collections::default();
+    let cloud_storage = json::parse_file(path_cloud_storage)?;
+
+    for entry in cloud_storage.as_array().unwrap_or_default() {
+        let Some([key, entry]) = entry.as_array() else {
+            continue;
+        };
+        if !key
+            .as_str()
+            .is_some_and(|k| k.starts_with("user-collections."))
+            || entry.get_bool("is_deleted") == Some(true)
+        {
+            continue;
+        }
+
+        let Some(collection) = entry.get_str("value").and_then(|v| {
+            json::parse(v)
+                .inspect_err(|e| warn!("{LAUNCHER} - Could not parse collection {key:?}: {e}"))
+                .ok()
+        }) else {
+            continue;
+        };
+
+        let get_app_ids = |key: &str| -> HashSet<u32> {
+            collection
+                .get_array(key)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|id| u32::try_from(id.as_u64()?).ok())
+                .collect()
+        };
+        let removed = get_app_ids("removed");
+
+        collections.add(
+            collection.get_str("id").unwrap_or_default(),
+            collection.get_str("name"),
+            get_app_ids("added")
+                .into_iter()
+                .filter(|id| !removed.contains(id)),
+        );
+    }
+
+    Ok(collections)
+}
+
+/// Parse collections from the tags of each app in `sharedconfig.vdf`, as used by older versions
+/// of Steam
+fn parse_shared_config_collections(
+    path_shared_config: &Path,
+) -> Result<Collections, GamesParsingError> {
+    let mut collections = Collections::default();
+    let shared_config = vdf::parse_file(path_shared_config)?;
+
+    for (app_id, app) in shared_config
+        .get_object_at(&[
+            "UserRoamingConfigStore",
+            "Software",
+            "Valve",
+            "Steam",
+            "apps",
+        ])
+        .into_iter()
+        .flat_map(KeyValues::iter)
+    {
+        let (Ok(app_id), Some(app)) = (app_id.parse::<u32>(), app.as_object()) else {
+            continue;
+        };
+
+        for tag in app
+            .get_object("tags")
+            .into_iter()
+            .flat_map(KeyValues::iter)
+            .filter_map(|(_, tag)| tag.as_str())
+        {
+            collections.add(tag, Some(tag), [app_id]);
+        }
+        if app.get_str("Hidden") == Some("1") {
+            collections.add("hidden", None, [app_id]);
+        }
+    }
+
+    Ok(collections)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    const STEAM_ID_1234: u64 = STEAM_ID_BASE + 1234;
+    const STEAM_ID_4321: u64 = STEAM_ID_BASE + 4321;
+
+    #[test]
+    fn test_get_users() -> Result<(), GamesParsingError> {
+        let path_steam_dir = get_mock_file_system_path().join(".local/share/Steam");
+        let users = get_users(&path_steam_dir)?;
+
+        assert_eq!(
+            users,
+            [
+                SteamUser {
+                    steam_id: STEAM_ID_1234,
+                    account_name: String::from("player_one"),
+                    persona_name: String::from("Player One"),
+                    is_most_recent: true,
+                    last_login: Some(1700000000),
+                },
+                SteamUser {
+                    steam_id: STEAM_ID_4321,
+                    account_name: String::from("player_two"),
+                    persona_name: String::from("Player Two"),
+                    is_most_recent: false,
+                    last_login: Some(1710000000),
+                },
+            ]
+        );
+        assert_eq!(users[0].get_account_id(), 1234);
+
+        Ok(())
+    }
+
+    #[test_case(SteamAccountFilter::All, &[STEAM_ID_1234, STEAM_ID_4321]; "all")]
+    #[test_case(SteamAccountFilter::MostRecent, &[STEAM_ID_1234]; "most recent")]
+    #[test_case(SteamAccountFilter::SteamId(STEAM_ID_4321), &[STEAM_ID_4321]; "steam id")]
+    #[test_case(SteamAccountFilter::SteamId(4321), &[STEAM_ID_4321]; "account id")]
+    #[test_case(SteamAccountFilter::SteamId(1), &[]; "unknown")]
+    fn test_get_userdata_dirs(filter: SteamAccountFilter, expected: &[u64]) {
+        let path_steam_dir = get_mock_file_system_path().join(".local/share/Steam");
+        let userdata_dirs = get_userdata_dirs(&path_steam_dir, &filter);
+
+        assert_eq!(
+            userdata_dirs.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            expected
+        );
+        for (steam_id, path_user_dir) in userdata_dirs {
+            assert_eq!(
+                path_user_dir,
+                path_steam_dir
+                    .join("userdata")
+                    .join(get_account_id(steam_id).to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_steam_users() {
+        let path_steam_dir = get_mock_file_system_path().join(".local/share/Steam");
+
+        // Played more recently by the second account, which also set custom art
+        let users = SteamUsers::new(&path_steam_dir, &SteamAccountFilter::All);
+        let data = users.get_app_data(2767030);
+        assert!(data.as_ref().is_some_and(|d| d.path_box_art.is_some()));
+        assert_eq!(
+            data.map(|d| (d.steam_id, d.last_played, d.playtime, d.launch_options)),
+            Some((
+                STEAM_ID_4321,
+                Some(1710000000),
+                Some(30),
+                Some(String::from("PROTON_USE_WINED3D=1 %command% -windowed"))
+            ))
+        );
+
+        // Only the first account is used
+        let users = SteamUsers::new(&path_steam_dir, &SteamAccountFilter::MostRecent);
+        assert_eq!(
+            users.get_app_data(2767030),
+            Some(UserAppData {
+                steam_id: STEAM_ID_1234,
+                last_played: Some(1700000000),
+                playtime: Some(120),
+                launch_options: Some(String::from("gamemoderun %command%")),
+                path_box_art: None,
+                collections: vec![String::from("Platformers"), String::from("Co-op")],
+                is_favorite: false,
+                is_hidden: false,
+                screenshots: vec![],
+                achievements: Some(Achievements {
+                    unlocked: 3,
+                    total: 40,
+                }),
+            })
+        );
+
+        assert!(users.get_app_data(620).is_none());
+    }
+
+    #[test]
+    fn test_get_screenshots() {
+        let path_user_dir = get_mock_file_system_path().join(".local/share/Steam/userdata/1234");
+        let path_screenshots_dir = path_user_dir.join("760/remote/105600/screenshots");
+
+        assert_eq!(
+            get_screenshots(&path_user_dir, 105600),
+            [
+                Screenshot {
+                    path: path_screenshots_dir.join("20231225093000_1.jpg"),
+                    path_thumbnail: None,
+                },
+                Screenshot {
+                    path: path_screenshots_dir.join("20240101120000_1.jpg"),
+                    path_thumbnail: Some(
+                        path_screenshots_dir.join("thumbnails/20240101120000_1.jpg")
+                    ),
+                },
+            ]
+        );
+        assert!(get_screenshots(&path_user_dir, 620).is_empty());
+    }
+
+    #[test]
+    fn test_get_achievements() {
+        let path_achievement_progress = get_mock_file_system_path()
+            .join(".local/share/Steam/userdata/1234/config/librarycache/achievement_progress.json");
+
+        // Apps without achievements are skipped
+        assert_eq!(
+            get_achievements(&path_achievement_progress),
+            HashMap::from([
+                (
+                    105600,
+                    Achievements {
+                        unlocked: 50,
+                        total: 88
+                    }
+                ),
+                (
+                    2767030,
+                    Achievements {
+                        unlocked: 3,
+                        total: 40
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_get_collections() {
+        let path_userdata_dir = get_mock_file_system_path().join(".local/share/Steam/userdata");
+
+        // Cloud storage, skipping deleted collections and removed apps
+        let collections = get_collections(&path_userdata_dir.join("1234"));
+        assert_eq!(
+            collections.names,
+            HashMap::from([
+                (105600, vec![String::from("Platformers")]),
+                (
+                    2767030,
+                    vec![String::from("Platformers"), String::from("Co-op")]
+                ),
+            ])
+        );
+        assert_eq!(collections.favorites, HashSet::from([105600]));
+        assert_eq!(collections.hidden, HashSet::from([1062090]));
+
+        // Tags in `sharedconfig.vdf`
+        let collections = get_collections(&path_userdata_dir.join("4321"));
+        assert_eq!(
+            collections.names,
+            HashMap::from([(2767030, vec![String::from("Shooters")])])
+        );
+        assert_eq!(collections.favorites, HashSet::from([2767030]));
+        assert_eq!(collections.hidden, HashSet::from([8930]));
+
+        assert_eq!(
+            get_collections(&path_userdata_dir.join("0")),
+            Collections::default()
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// PATHS:
+// - <library>/steamapps/workshop/appworkshop_<app id>.acf
+// - <library>/steamapps/workshop/content/<app id>/<item id>/
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use tracing::warn;
+
+use super::get_steamapps_dir;
+use crate::{
+    data::SupportedLaunchers,
+    error::GamesParsingError,
+    macros::logs::debug_path,
+    utils::some_if_dir,
+    vdf::{self, KeyValues},
+};
+
+const LAUNCHER: SupportedLaunchers = SupportedLaunchers::Steam;
+
+/// An item from the Steam Workshop, e.g. a mod, which a user has subscribed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct WorkshopItem {
+    /// ID of the item on the Workshop.
+    pub item_id: u64,
+    /// App ID of the game the item is for.
+    pub app_id: u32,
+    /// Path to the directory containing the item's files, if it has been downloaded.
+    pub path: Option<PathBuf>,
+    /// Size of the item's files in bytes, or `0` if it hasn't been downloaded.
+    pub size: u64,
+    /// When the downloaded version of the item was last updated on the Workshop, as a Unix
+    /// timestamp in seconds.
+    pub time_updated: Option<u64>,
+    /// When the latest version of the item was last updated on the Workshop. If this is after
+    /// `time_updated`, an update needs to be downloaded.
+    pub time_updated_latest: Option<u64>,
+    /// Account ID of the user who subscribed to the item.
+    pub subscribed_by: Option<u32>,
+}
+
+impl WorkshopItem {
+    /// Returns `true` if the item hasn't been downloaded yet, or a newer version is available.
+    #[must_use]
+    pub fn needs_update(&self) -> bool {
+        self.path.is_none() || self.time_updated_latest > self.time_updated
+    }
+}
+
+/// Get all Workshop items of the given app in the library at the given path, which are listed in
+/// `appworkshop_<app id>.acf`
+#[tracing::instrument(level = "trace")]
+pub(super) fn get_workshop_items(
+    path_library: &Path,
+    app_id: u32,
+) -> Result<Vec<WorkshopItem>, GamesParsingError> {
+    let path_workshop_dir = get_steamapps_dir(path_library).join("workshop");
+    let path_app_workshop = path_workshop_dir.join(format!("appworkshop_{app_id}.acf"));
+
+    debug_path!("app workshop manifest", path_app_workshop);
+
+    if !path_app_workshop.is_file() {
+        return Ok(vec![]);
+    }
+
+    let app_workshop = vdf::parse_file(&path_app_workshop)?;
+    let Some(app_workshop) = app_workshop.get_object("AppWorkshop") else {
+        warn!("{LAUNCHER} - No `AppWorkshop` block found in {path_app_workshop:?}");
+        return Ok(vec![]);
+    };
+
+    let get_items = |key: &str| -> BTreeMap<u64, &KeyValues> {
+        app_workshop
+            .get_object(key)
+            .into_iter()
+            .flat_map(KeyValues::iter)
+            .filter_map(|(item_id, item)| Some((item_id.parse().ok()?, item.as_object()?)))
+            .collect()
+    };
+    let installed = get_items("WorkshopItemsInstalled");
+    let details = get_items("WorkshopItemDetails");
+
+    // Items which haven't been downloaded yet are only listed in the details
+    let item_ids = installed
+        .keys()
+        .chain(details.keys())
+        .copied()
+        .collect::<BTreeSet<_>>();
+
+    let path_content_dir = path_workshop_dir.join("content").join(app_id.to_string());
+
+    Ok(item_ids
+        .into_iter()
+        .map(|item_id| {
+            let installed = installed.get(&item_id);
+            let details = details.get(&item_id);
+
+            WorkshopItem {
+                item_id,
+                app_id,
+                path: installed
+                    .and_then(|_| some_if_dir(path_content_dir.join(item_id.to_string()))),
+                size: installed.and_then(|i| i.get_parsed("size")).unwrap_or(0),
+                time_updated: installed
+                    .and_then(|i| i.get_parsed("timeupdated"))
+                    .or_else(|| details.and_then(|d| d.get_parsed("timeupdated")))
+                    .filter(|t| *t > 0),
+                time_updated_latest: details
+                    .and_then(|d| d.get_parsed("latest_timeupdated"))
+                    .filter(|t| *t > 0),
+                subscribed_by: details.and_then(|d| d.get_parsed("subscribedby")),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test]
+    fn test_get_workshop_items() -> Result<(), GamesParsingError> {
+        let path_library = get_mock_file_system_path().join("steam_libraries/2");
+        let items = get_workshop_items(&path_library, 105600)?;
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[0],
+            WorkshopItem {
+                item_id: 2563309347,
+                app_id: 105600,
+                path: Some(path_library.join("steamapps/workshop/content/105600/2563309347")),
+                size: 5242880,
+                time_updated: Some(1700000000),
+                time_updated_latest: Some(1700000000),
+                subscribed_by: Some(1234),
+            }
+        );
+        assert!(!items[0].needs_update());
+
+        // Outdated
+        assert_eq!(items[1].time_updated, Some(1690000000));
+        assert!(items[1].path.is_some());
+        assert!(items[1].needs_update());
+
+        // Subscribed, but not downloaded yet
+        assert_eq!(items[2].item_id, 3042181512);
+        assert!(items[2].path.is_none());
+        assert_eq!(items[2].size, 0);
+        assert_eq!(items[2].subscribed_by, Some(4321));
+        assert!(items[2].needs_update());
+
+        // No Workshop items
+        assert!(get_workshop_items(&path_library, 620)?.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,20 +1,27 @@
-use std::sync::Arc;
+use std::{env, path::PathBuf, sync::Arc};
 
-use dirs::{cache_dir, config_dir, data_dir, home_dir};
 use launchers::heroic::sideload::HeroicSideload;
 use tracing::error;
 
 use self::launchers::{
     bottles::Bottles,
+    desktop_entries::DesktopEntries,
+    flatpak::Flatpak,
     heroic::{amazon::HeroicAmazon, epic::HeroicEpic, gog::HeroicGOG},
     itch::Itch,
     lutris::Lutris,
     minecraft::{at::MinecraftAT, prism::MinecraftPrism},
-    steam::{Steam, SteamShortcuts},
+    snap::Snap,
+    steam::{Steam, SteamShortcuts, find_steam_dir_candidates, get_distinct_steam_dirs},
+};
+use self::sandbox::{HostDirs, HostLauncher, is_flatpak_sandbox};
+use crate::data::{
+    DetectorOptions, Game, GamesDetector, GamesPerLauncher, InstallVariant, Launcher, Launchers,
+    SupportedLaunchers,
 };
-use crate::data::{Game, GamesDetector, GamesPerLauncher, Launchers, SupportedLaunchers};
 
-mod launchers;
+pub(crate) mod launchers;
+mod sandbox;
 
 #[derive(Debug)]
 pub struct GamesDetectorLinux {
@@ -23,39 +30,191 @@ pub struct GamesDetectorLinux {
 
 impl Default for GamesDetectorLinux {
     fn default() -> Self {
-        let launchers = GamesDetectorLinux::get_supported_launchers();
-        GamesDetectorLinux { launchers }
+        GamesDetectorLinux::new(DetectorOptions::default())
     }
 }
 
 impl GamesDetectorLinux {
-    pub fn get_supported_launchers() -> Launchers {
-        let path_home = home_dir().expect("Failed to find the user's home directory");
-        let path_config = config_dir().expect("Failed to find the user's config directory");
-        let path_cache = cache_dir().expect("Failed to find the user's cache directory");
-        let path_data = data_dir().expect("Failed to find the user's data directory");
-
-        vec![
-            Arc::new(Steam::new(&path_home, &path_data)),
-            Arc::new(SteamShortcuts::new(&path_home, &path_data)),
-            Arc::new(HeroicGOG::new(&path_home, &path_config)),
-            Arc::new(HeroicEpic::new(&path_home, &path_config)),
-            Arc::new(HeroicAmazon::new(&path_home, &path_config)),
-            Arc::new(HeroicSideload::new(&path_home, &path_config)),
-            Arc::new(Lutris::new(
-                &path_home,
-                &path_config,
-                &path_cache,
-                &path_data,
-            )),
-            Arc::new(Bottles::new(&path_home, &path_data)),
-            Arc::new(MinecraftPrism::new(&path_home, &path_data)),
-            Arc::new(MinecraftAT::new(&path_home, &path_data)),
-            Arc::new(Itch::new(&path_home, &path_config)),
-        ]
+    pub fn new(options: DetectorOptions) -> Self {
+        let is_sandboxed = is_flatpak_sandbox();
+        let spawn_on_host = is_sandboxed && options.spawn_on_host;
+
+        let launchers =
+            GamesDetectorLinux::get_supported_launchers(&HostDirs::new(is_sandboxed), &options)
+                .into_iter()
+                .map(|l| -> Arc<dyn Launcher> {
+                    if spawn_on_host {
+                        Arc::new(HostLauncher::new(l))
+                    } else {
+                        Arc::<dyn Launcher + Sync>::from(l)
+                    }
+                })
+                .collect();
+
+        GamesDetectorLinux { launchers }
+    }
+
+    pub fn get_supported_launchers(
+        host_dirs: &HostDirs,
+        options: &DetectorOptions,
+    ) -> Vec<Box<dyn Launcher + Sync>> {
+        let HostDirs {
+            path_home,
+            path_config,
+            path_cache,
+            path_data,
+            paths_data_dirs,
+            path_flatpak_system,
+            path_snap,
+        } = host_dirs;
+
+        let all_variants = [
+            InstallVariant::Native,
+            InstallVariant::Flatpak,
+            InstallVariant::Snap,
+        ];
+        let native_and_flatpak = &all_variants[..2];
+        let variants_for = |sources: &[SupportedLaunchers], variants: &[InstallVariant]| {
+            get_install_variants(variants, sources, &options.launcher_executables)
+        };
+        let heroic_variants = variants_for(
+            &[
+                SupportedLaunchers::HeroicGamesGOG,
+                SupportedLaunchers::HeroicGamesEpic,
+                SupportedLaunchers::HeroicGamesAmazon,
+                SupportedLaunchers::HeroicGamesSideload,
+            ],
+            &all_variants,
+        );
+        let mut launchers: Vec<Box<dyn Launcher + Sync>> = Vec::new();
+
+        // Steam can be installed in several places for the same install variant, so a launcher is
+        // created for each distinct Steam directory instead
+        let steam_executable = get_launcher_executable(
+            &[
+                SupportedLaunchers::Steam,
+                SupportedLaunchers::SteamShortcuts,
+            ],
+            &options.launcher_executables,
+        );
+        let steam_dirs = get_distinct_steam_dirs(find_steam_dir_candidates(
+            path_home,
+            path_data,
+            &options.steam_dirs,
+            |key| env::var(key).ok(),
+        ))
+        .into_iter()
+        .map(|mut steam_dir| {
+            if let (Some(variant), InstallVariant::Native) =
+                (&steam_executable, &steam_dir.install_variant)
+            {
+                steam_dir.install_variant = variant.clone();
+            }
+            steam_dir
+        })
+        .collect::<Vec<_>>();
+        launchers.extend(get_launcher_per_variant(&steam_dirs, |d| {
+            Steam::new(d, options.steam_accounts.clone(), options.include_hidden)
+        }));
+        launchers.extend(get_launcher_per_variant(&steam_dirs, |d| {
+            SteamShortcuts::new(d, options.steam_accounts.clone(), options.include_hidden)
+        }));
+        launchers.extend(get_launcher_per_variant(&heroic_variants, |v| {
+            HeroicGOG::new(path_home, path_config, v)
+        }));
+        launchers.extend(get_launcher_per_variant(&heroic_variants, |v| {
+            HeroicEpic::new(path_home, path_config, v)
+        }));
+        launchers.extend(get_launcher_per_variant(&heroic_variants, |v| {
+            HeroicAmazon::new(path_home, path_config, v)
+        }));
+        launchers.extend(get_launcher_per_variant(&heroic_variants, |v| {
+            HeroicSideload::new(path_home, path_config, v)
+        }));
+        let lutris_variants = variants_for(&[SupportedLaunchers::Lutris], &all_variants);
+        launchers.extend(get_launcher_per_variant(&lutris_variants, |v| {
+            Lutris::new(path_home, path_config, path_cache, path_data, v)
+        }));
+        let bottles_variants = variants_for(&[SupportedLaunchers::Bottles], &all_variants);
+        launchers.extend(get_launcher_per_variant(&bottles_variants, |v| {
+            Bottles::new(path_home, path_data, v)
+        }));
+        let prism_variants =
+            variants_for(&[SupportedLaunchers::MinecraftPrism], native_and_flatpak);
+        launchers.extend(get_launcher_per_variant(&prism_variants, |v| {
+            MinecraftPrism::new(path_home, path_data, v)
+        }));
+        let at_variants = variants_for(&[SupportedLaunchers::MinecraftAT], native_and_flatpak);
+        launchers.extend(get_launcher_per_variant(&at_variants, |v| {
+            MinecraftAT::new(path_home, path_data, v)
+        }));
+        let itch_variants = variants_for(&[SupportedLaunchers::Itch], &all_variants);
+        launchers.extend(get_launcher_per_variant(&itch_variants, |v| {
+            Itch::new(path_home, path_config, v)
+        }));
+        launchers.push(Box::new(DesktopEntries::new(path_data, paths_data_dirs)));
+        launchers.push(Box::new(Flatpak::new(path_data, path_flatpak_system)));
+        launchers.push(Box::new(Snap::new(path_snap)));
+
+        launchers
     }
 }
 
+/// Get the install variant of the executable given in the options for any of a launcher's sources
+fn get_launcher_executable(
+    sources: &[SupportedLaunchers],
+    launcher_executables: &[(SupportedLaunchers, PathBuf)],
+) -> Option<InstallVariant> {
+    launcher_executables
+        .iter()
+        .find(|(source, _)| sources.contains(source))
+        .map(|(_, path)| InstallVariant::from_executable(path.clone()))
+}
+
+/// Get the install variants to search for a launcher in. If an executable is given for any of the
+/// launcher's sources, it replaces the native variant, as it uses the same data.
+fn get_install_variants(
+    variants: &[InstallVariant],
+    sources: &[SupportedLaunchers],
+    launcher_executables: &[(SupportedLaunchers, PathBuf)],
+) -> Vec<InstallVariant> {
+    let executable = get_launcher_executable(sources, launcher_executables);
+
+    variants
+        .iter()
+        .map(|variant| match (variant, &executable) {
+            (InstallVariant::Native, Some(executable)) => executable.clone(),
+            _ => variant.clone(),
+        })
+        .collect()
+}
+
+/// Create a launcher for each of the given install variants (or, for Steam, each Steam directory),
+/// keeping only those which are detected. If none are detected, the launcher for the first variant
+/// is kept so that it is still listed as supported.
+fn get_launcher_per_variant<V: Clone, L: Launcher + Sync + 'static>(
+    install_variants: &[V],
+    new_launcher: impl Fn(V) -> L,
+) -> Vec<Box<dyn Launcher + Sync>> {
+    let mut launchers: Vec<Box<dyn Launcher + Sync>> = Vec::new();
+    let mut fallback = None;
+
+    for install_variant in install_variants {
+        let launcher = new_launcher(install_variant.clone());
+        if launcher.is_detected() {
+            launchers.push(Box::new(launcher));
+        } else if fallback.is_none() {
+            fallback = Some(launcher);
+        }
+    }
+
+    if launchers.is_empty() {
+        launchers.extend(fallback.map(|l| Box::new(l) as Box<dyn Launcher + Sync>));
+    }
+
+    launchers
+}
+
 impl GamesDetector for GamesDetectorLinux {
     fn get_detected_launchers(&self) -> Launchers {
         self.launchers
@@ -82,36 +241,49 @@ impl GamesDetector for GamesDetectorLinux {
     }
 
     fn get_all_detected_games_per_launcher(&self) -> GamesPerLauncher {
-        self.get_detected_launchers()
-            .into_iter()
-            .filter_map(|l| {
-                if let Ok(g) = l.get_detected_games() {
-                    Some((l.get_launcher_type(), g))
-                } else {
-                    error!("Could not get games for launcher: {l:?}");
-                    None
-                }
-            })
-            .collect::<GamesPerLauncher>()
+        let mut games_per_launcher = GamesPerLauncher::new();
+
+        for l in self.get_detected_launchers() {
+            let Ok(games) = l.get_detected_games() else {
+                error!("Could not get games for launcher: {l:?}");
+                continue;
+            };
+
+            let launcher_type = l.get_launcher_type();
+            match games_per_launcher
+                .iter_mut()
+                .find(|(t, _)| *t == launcher_type)
+            {
+                Some((_, existing_games)) => existing_games.extend(games),
+                None => games_per_launcher.push((launcher_type, games)),
+            }
+        }
+
+        games_per_launcher
     }
 
     fn get_all_detected_games_from_specific_launcher(
         &self,
         launcher_type: SupportedLaunchers,
     ) -> Option<Vec<Game>> {
-        self.get_detected_launchers()
+        let games_per_variant: Vec<Vec<Game>> = self
+            .get_detected_launchers()
             .into_iter()
-            .find(|l| l.get_launcher_type() == launcher_type)
-            .and_then(|l| {
+            .filter(|l| l.get_launcher_type() == launcher_type)
+            .filter_map(|l| {
                 l.get_detected_games()
                     .map_err(|_| {
                         error!(
-                            "Launcher detected but there was an error with getting detected games for the launcher: {:?}",
-                            l.get_launcher_type()
+                            "Launcher detected but there was an error with getting detected games for the launcher: {:?} ({:?})",
+                            l.get_launcher_type(),
+                            l.get_install_variant()
                         )
                     })
                     .ok()
             })
+            .collect();
+
+        (!games_per_variant.is_empty()).then(|| games_per_variant.into_iter().flatten().collect())
     }
 }
 
@@ -124,3 +296,136 @@ pub mod test_utils {
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/file_system_mocks/linux")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{test_utils::get_mock_file_system_path, *};
+    use crate::data::SteamAccountFilter;
+
+    #[test]
+    fn test_launcher_per_variant() {
+        let path_file_system_mock = get_mock_file_system_path();
+        let get_steam_dirs = |path_home: &Path, path_data: &Path| {
+            get_distinct_steam_dirs(find_steam_dir_candidates(path_home, path_data, &[], |_| {
+                None
+            }))
+        };
+
+        // Both a native and a flatpak install of Steam exist in the mock file system
+        let steam_dirs = get_steam_dirs(
+            &path_file_system_mock,
+            &path_file_system_mock.join(".local/share"),
+        );
+        let launchers = get_launcher_per_variant(&steam_dirs, |d| {
+            Steam::new(d, SteamAccountFilter::All, true)
+        });
+        assert_eq!(
+            launchers
+                .iter()
+                .map(|l| l.get_install_variant())
+                .collect::<Vec<_>>(),
+            [InstallVariant::Native, InstallVariant::Flatpak]
+        );
+
+        // Undetected launchers are still kept, using the first variant
+        let path_invalid = path_file_system_mock.join("invalid/path");
+        let steam_dirs = get_steam_dirs(&path_invalid, &path_invalid);
+        let launchers = get_launcher_per_variant(&steam_dirs, |d| {
+            Steam::new(d, SteamAccountFilter::All, true)
+        });
+        assert_eq!(launchers.len(), 1);
+        assert!(!launchers[0].is_detected());
+        assert_eq!(launchers[0].get_install_variant(), InstallVariant::Native);
+    }
+
+    #[test]
+    fn test_get_install_variants() {
+        let variants = [InstallVariant::Native, InstallVariant::Flatpak];
+        let launcher_executables = [
+            (
+                SupportedLaunchers::HeroicGamesEpic,
+                PathBuf::from("/apps/Heroic.AppImage"),
+            ),
+            (
+                SupportedLaunchers::Lutris,
+                PathBuf::from("/opt/lutris/bin/lutris"),
+            ),
+        ];
+
+        // An executable given for any of a launcher's sources replaces its native variant
+        assert_eq!(
+            get_install_variants(
+                &variants,
+                &[
+                    SupportedLaunchers::HeroicGamesGOG,
+                    SupportedLaunchers::HeroicGamesEpic
+                ],
+                &launcher_executables
+            ),
+            [
+                InstallVariant::AppImage(PathBuf::from("/apps/Heroic.AppImage")),
+                InstallVariant::Flatpak
+            ]
+        );
+        assert_eq!(
+            get_install_variants(
+                &variants,
+                &[SupportedLaunchers::Lutris],
+                &launcher_executables
+            ),
+            [
+                InstallVariant::Custom(PathBuf::from("/opt/lutris/bin/lutris")),
+                InstallVariant::Flatpak
+            ]
+        );
+        assert_eq!(
+            get_install_variants(
+                &variants,
+                &[SupportedLaunchers::Bottles],
+                &launcher_executables
+            ),
+            variants
+        );
+    }
+
+    #[test]
+    fn test_custom_executable_launcher() -> Result<(), crate::error::GamesParsingError> {
+        let path_file_system_mock = get_mock_file_system_path();
+        let host_dirs = HostDirs {
+            path_home: path_file_system_mock.clone(),
+            path_config: path_file_system_mock.join(".config"),
+            path_cache: path_file_system_mock.join(".cache"),
+            path_data: path_file_system_mock.join(".local/share"),
+            paths_data_dirs: Vec::new(),
+            path_flatpak_system: path_file_system_mock.join("var/lib/flatpak"),
+            path_snap: path_file_system_mock.join("snap"),
+        };
+        let path_heroic = PathBuf::from("/apps/Heroic.AppImage");
+        let options = DetectorOptions {
+            launcher_executables: vec![(SupportedLaunchers::HeroicGamesEpic, path_heroic.clone())],
+            ..Default::default()
+        };
+
+        let launchers = GamesDetectorLinux::get_supported_launchers(&host_dirs, &options);
+        let epic = launchers
+            .iter()
+            .find(|l| {
+                l.get_launcher_type() == SupportedLaunchers::HeroicGamesEpic
+                    && l.get_install_variant() == InstallVariant::AppImage(path_heroic.clone())
+            })
+            .expect("the Heroic AppImage should be detected");
+
+        // Games are read from the native data dir, and launched with the AppImage
+        let games = epic.get_detected_games()?;
+        assert!(!games.is_empty());
+        assert!(
+            games
+                .iter()
+                .all(|g| g.launch_command.get_program() == path_heroic.as_os_str())
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+// PATHS:
+// - /.flatpak-info
+// - /run/host/ (host file system, when running inside a flatpak sandbox)
+// - /var/lib/flatpak/ (system flatpak installation)
+// - /snap/
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use dirs::{cache_dir, config_dir, data_dir, home_dir};
+use tracing::{debug, trace};
+
+use crate::{
+    data::{GamesResult, InstallVariant, Launcher, SupportedLaunchers},
+    utils::get_launch_command_flatpak_spawn_host,
+};
+
+/// File which flatpak creates at the root of every sandbox
+const PATH_FLATPAK_INFO: &str = "/.flatpak-info";
+/// Where directories of the host such as `/usr` are mounted within a flatpak sandbox
+const PATH_HOST_ROOT: &str = "/run/host";
+/// System-wide flatpak installation
+const PATH_FLATPAK_SYSTEM: &str = "/var/lib/flatpak";
+/// Where snaps are mounted
+const PATH_SNAP: &str = "/snap";
+
+/// Returns `true` if the current process is running inside a flatpak sandbox
+pub fn is_flatpak_sandbox() -> bool {
+    Path::new(PATH_FLATPAK_INFO).is_file()
+}
+
+// HOST DIRECTORIES ---------------------------------------------------------------------
+/// The user's directories on the host, which may differ from those seen by the current process
+/// if it is running inside a flatpak sandbox
+#[derive(Debug)]
+pub struct HostDirs {
+    pub path_home: PathBuf,
+    pub path_config: PathBuf,
+    pub path_cache: PathBuf,
+    pub path_data: PathBuf,
+    pub paths_data_dirs: Vec<PathBuf>,
+    pub path_flatpak_system: PathBuf,
+    pub path_snap: PathBuf,
+}
+
+impl HostDirs {
+    pub fn new(is_sandboxed: bool) -> Self {
+        let path_home = home_dir().expect("Failed to find the user's home directory");
+
+        if is_sandboxed {
+            return Self::from_sandbox(path_home, |key| env::var(key).ok());
+        }
+
+        Self {
+            path_home,
+            path_config: config_dir().expect("Failed to find the user's config directory"),
+            path_cache: cache_dir().expect("Failed to find the user's cache directory"),
+            path_data: data_dir().expect("Failed to find the user's data directory"),
+            paths_data_dirs: get_data_dirs(env::var("XDG_DATA_DIRS").ok()),
+            path_flatpak_system: PathBuf::from(PATH_FLATPAK_SYSTEM),
+            path_snap: PathBuf::from(PATH_SNAP),
+        }
+    }
+
+    /// Resolve the host's directories from within a flatpak sandbox. Flatpak points the `XDG_*`
+    /// variables at the app's own directories under `~/.var/app/`, but exposes the host's values
+    /// as `HOST_XDG_*`, which are used if available.
+    fn from_sandbox(path_home: PathBuf, get_env: impl Fn(&str) -> Option<String>) -> Self {
+        let get_dir = |key: &str, default: &str| {
+            get_env(key)
+                .filter(|p| Path::new(p).is_absolute())
+                .map_or_else(|| path_home.join(default), PathBuf::from)
+        };
+
+        let path_config = get_dir("HOST_XDG_CONFIG_HOME", ".config");
+        let path_cache = get_dir("HOST_XDG_CACHE_HOME", ".cache");
+        let path_data = get_dir("HOST_XDG_DATA_HOME", ".local/share");
+
+        // The host's `XDG_DATA_DIRS` is not exposed, so use the defaults within the host mount
+        let paths_data_dirs = get_data_dirs(None)
+            .iter()
+            .map(|p| get_host_path(p))
+            .collect();
+
+        // System directories are only visible within the host mount, if the sandbox has been given
+        // access to them (e.g. with `--filesystem=host-os`)
+        let path_flatpak_system = get_host_path(Path::new(PATH_FLATPAK_SYSTEM));
+        let path_snap = get_host_path(Path::new(PATH_SNAP));
+        for (name, path) in [
+            ("system flatpak", &path_flatpak_system),
+            ("snap", &path_snap),
+        ] {
+            if !path.is_dir() {
+                debug!(
+                    "The host's {name} directory isn't accessible from the flatpak sandbox at {path:?}, so {name} games won't be detected"
+                );
+            }
+        }
+
+        let host_dirs = Self {
+            path_home,
+            path_config,
+            path_cache,
+            path_data,
+            paths_data_dirs,
+            path_flatpak_system,
+            path_snap,
+        };
+
+        debug!("Running inside a flatpak sandbox, using host directories: {host_dirs:?}");
+
+        host_dirs
+    }
+}
+
+/// Get the path of the given host path within the host mount of a flatpak sandbox
+fn get_host_path(path: &Path) -> PathBuf {
+    Path::new(PATH_HOST_ROOT).join(path.strip_prefix("/").unwrap_or(path))
+}
+
+/// Get the directories listed in the given value of `$XDG_DATA_DIRS`, falling back to the
+/// defaults given by the XDG Base Directory spec
+fn get_data_dirs(xdg_data_dirs: Option<String>) -> Vec<PathBuf> {
+    xdg_data_dirs
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"))
+        .split(':')
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+// HOST LAUNCHER ------------------------------------------------------------------------
+/// Wraps a [`Launcher`] so that the launch commands of its games are run on the host, using
+/// `flatpak-spawn --host`, instead of inside the flatpak sandbox
+#[derive(Debug)]
+pub struct HostLauncher {
+    launcher: Box<dyn Launcher + Sync>,
+}
+
+impl HostLauncher {
+    pub fn new(launcher: Box<dyn Launcher + Sync>) -> Self {
+        Self { launcher }
+    }
+}
+
+impl Launcher for HostLauncher {
+    fn get_launcher_type(&self) -> SupportedLaunchers {
+        self.launcher.get_launcher_type()
+    }
+
+    fn get_install_variant(&self) -> InstallVariant {
+        self.launcher.get_install_variant()
+    }
+
+    fn is_detected(&self) -> bool {
+        self.launcher.is_detected()
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn get_detected_games(&self) -> GamesResult {
+        let mut games = self.launcher.get_detected_games()?;
+
+        for game in &mut games {
+            game.launch_command = get_launch_command_flatpak_spawn_host(&game.launch_command);
+            trace!(
+                "{} - launch command for '{}' on the host: {:?}",
+                self.get_launcher_type(),
+                game.title,
+                game.launch_command
+            );
+        }
+
+        Ok(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        data::{Game, GameMetadata},
+        error::GamesParsingError,
+        linux::test_utils::get_mock_file_system_path,
+        utils::get_launch_command,
+    };
+
+    #[test]
+    fn test_host_dirs_from_sandbox() {
+        let path_home = PathBuf::from("/home/user");
+        let env_vars = HashMap::from([
+            ("HOST_XDG_CONFIG_HOME", "/home/user/custom/config"),
+            ("HOST_XDG_DATA_HOME", "relative/paths/are/invalid"),
+        ]);
+
+        let host_dirs = HostDirs::from_sandbox(path_home.clone(), |key| {
+            env_vars.get(key).map(|v| v.to_string())
+        });
+
+        assert_eq!(host_dirs.path_home, path_home);
+        assert_eq!(host_dirs.path_config, path_home.join("custom/config"));
+        assert_eq!(host_dirs.path_cache, path_home.join(".cache"));
+        assert_eq!(host_dirs.path_data, path_home.join(".local/share"));
+        assert_eq!(
+            host_dirs.paths_data_dirs,
+            [
+                PathBuf::from("/run/host/usr/local/share"),
+                PathBuf::from("/run/host/usr/share")
+            ]
+        );
+        assert_eq!(
+            host_dirs.path_flatpak_system,
+            PathBuf::from("/run/host/var/lib/flatpak")
+        );
+        assert_eq!(host_dirs.path_snap, PathBuf::from("/run/host/snap"));
+    }
+
+    #[test]
+    fn test_get_data_dirs() {
+        assert_eq!(
+            get_data_dirs(Some(String::from("/a::/b/c"))),
+            [PathBuf::from("/a"), PathBuf::from("/b/c")]
+        );
+        assert_eq!(get_data_dirs(Some(String::new())).len(), 2);
+    }
+
+    #[derive(Debug)]
+    struct MockLauncher;
+
+    impl Launcher for MockLauncher {
+        fn get_launcher_type(&self) -> SupportedLaunchers {
+            SupportedLaunchers::DesktopEntries
+        }
+
+        fn get_install_variant(&self) -> InstallVariant {
+            InstallVariant::Native
+        }
+
+        fn is_detected(&self) -> bool {
+            true
+        }
+
+        fn get_detected_games(&self) -> GamesResult {
+            let mut launch_command = get_launch_command(
+                "lutris",
+                ["lutris:rungameid/1"],
+                [("LUTRIS_SKIP_INIT", "1")],
+            );
+            launch_command.current_dir(get_mock_file_system_path());
+
+            Ok(vec![Game {
+                title: String::from("Game"),
+                path_icon: None,
+                path_box_art: None,
+                path_game_dir: None,
+                launch_command,
+                source: self.get_launcher_type(),
+                install_variant: self.get_install_variant(),
+                metadata: GameMetadata::default(),
+            }])
+        }
+    }
+
+    #[test]
+    fn test_host_launcher() -> Result<(), GamesParsingError> {
+        let launcher = HostLauncher::new(Box::new(MockLauncher));
+
+        assert!(launcher.is_detected());
+        assert_eq!(launcher.get_install_variant(), InstallVariant::Native);
+
+        let games = launcher.get_detected_games()?;
+        let launch_command = &games[0].launch_command;
+
+        assert_eq!(launch_command.get_program(), "flatpak-spawn");
+        assert_eq!(
+            launch_command
+                .get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect::<Vec<_>>(),
+            [
+                "--host".to_string(),
+                format!("--directory={}", get_mock_file_system_path().display()),
+                "--env=LUTRIS_SKIP_INIT=1".to_string(),
+                "lutris".to_string(),
+                "lutris:rungameid/1".to_string(),
+            ]
+        );
+
+        Ok(())
+    }
+}
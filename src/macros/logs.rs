@@ -1,7 +1,11 @@
 macro_rules! debug_path {
     ($description: expr, $path: ident) => {
+        debug_path!(LAUNCHER, $description, $path)
+    };
+    ($launcher: expr, $description: expr, $path: ident) => {
         tracing::debug!(
-            "{LAUNCHER} - {} exists at {:?}: {}",
+            "{} - {} exists at {:?}: {}",
+            $launcher,
             $description,
             $path,
             $path.exists()
@@ -10,13 +14,6 @@ macro_rules! debug_path {
 }
 pub(crate) use debug_path;
 
-macro_rules! debug_fallback_flatpak {
-    () => {
-        tracing::debug!("{LAUNCHER} - Attempting to fall back to flatpak");
-    };
-}
-pub(crate) use debug_fallback_flatpak;
-
 macro_rules! warn_no_games {
     () => {
         tracing::warn!("{LAUNCHER} - Detected but no games found");
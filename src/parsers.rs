@@ -1,7 +1,9 @@
 use nom::{
     AsChar, IResult, Parser,
-    bytes::complete::{is_not, tag, take_till, take_until},
-    character::complete::{alpha1, char},
+    branch::alt,
+    bytes::complete::{is_not, tag, take_till, take_until, take_while_m_n},
+    character::complete::{char, digit1, one_of},
+    combinator::{eof, map_res, opt, value, verify},
     sequence::{delimited, preceded},
 };
 // GENERAL ----------------------------------------------------------------------------------------
@@ -72,20 +74,6 @@ pub fn parse_value_json<'a>(file_content: &'a str, key: &'a str) -> IResult<&'a
     Ok((file_content, value.to_owned()))
 }
 
-/// Find the next occurrence of a key in a `.json` file and returns the matching value (unquoted)
-/// e.g. "keyName": false
-pub fn parse_value_json_unquoted<'a>(
-    file_content: &'a str,
-    key: &'a str,
-) -> IResult<&'a str, String> {
-    let (file_content, matched_key) = parse_until_key_json(file_content, key)?;
-    let (file_content, _) = tag(matched_key.as_str()).parse(file_content)?;
-
-    let (file_content, value) = preceded(parse_not_alphanumeric, alpha1).parse(file_content)?;
-
-    Ok((file_content, value.to_owned()))
-}
-
 /// Find the next occurrence of a key in a `.yml` file and returns the matching value
 /// e.g. keyName: value
 pub fn parse_value_yml<'a>(file_content: &'a str, key: &'a str) -> IResult<&'a str, String> {
@@ -110,6 +98,108 @@ pub fn parse_value_cfg<'a>(file_content: &'a str, key: &'a str) -> IResult<&'a s
     Ok((file_content, value.to_owned()))
 }
 
+// DATES ------------------------------------------------------------------------------------------
+/// Parses a fixed number of digits as a number, e.g. the month in a date
+fn parse_digits(count: usize) -> impl FnMut(&str) -> IResult<&str, i64> {
+    move |input| {
+        map_res(
+            take_while_m_n(count, count, |c: char| c.is_ascii_digit()),
+            str::parse,
+        )
+        .parse(input)
+    }
+}
+
+/// Parses a fixed number of digits as a number, failing if it isn't within the given range, e.g.
+/// `1..=12` for the month in a date
+fn parse_digits_in_range(
+    count: usize,
+    range: std::ops::RangeInclusive<i64>,
+) -> impl FnMut(&str) -> IResult<&str, i64> {
+    move |input| verify(parse_digits(count), |n| range.contains(n)).parse(input)
+}
+
+/// Parses the UTC offset at the end of an ISO 8601 date-time as a number of seconds, e.g. `Z` or
+/// `+01:00`
+fn parse_utc_offset(input: &str) -> IResult<&str, i64> {
+    alt((
+        value(0, char('Z')),
+        (
+            one_of("+-"),
+            parse_digits_in_range(2, 0..=23),
+            opt(char(':')),
+            parse_digits_in_range(2, 0..=59),
+        )
+            .map(|(sign, hours, _, minutes)| {
+                let offset = hours * 3600 + minutes * 60;
+                if sign == '-' { -offset } else { offset }
+            }),
+    ))
+    .parse(input)
+}
+
+/// Get the number of days in the given month (from 1 to 12) of the given year
+fn get_days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Get the number of days between the Unix epoch and the given date, using the proleptic Gregorian
+/// calendar
+fn get_days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    // Count years from March, so that the leap day is at the end of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Parses an ISO 8601 date-time as a Unix timestamp in seconds, e.g. `2024-03-10T21:45:30.123Z`.
+/// Fractional seconds are dropped, and a missing UTC offset is taken to be UTC. Dates and times
+/// with out of range fields (e.g. `2024-02-30` or `24:00:00`) are rejected.
+pub fn parse_iso_8601_timestamp(input: &str) -> IResult<&str, u64> {
+    let (input, (year, month)) = (
+        parse_digits(4),
+        preceded(char('-'), parse_digits_in_range(2, 1..=12)),
+    )
+        .parse(input)?;
+    let (input, day) = preceded(
+        char('-'),
+        parse_digits_in_range(2, 1..=get_days_in_month(year, month)),
+    )
+    .parse(input)?;
+    let (input, (_, hours, _, minutes, _, seconds)) = (
+        one_of("T "),
+        parse_digits_in_range(2, 0..=23),
+        char(':'),
+        parse_digits_in_range(2, 0..=59),
+        char(':'),
+        parse_digits_in_range(2, 0..=59),
+    )
+        .parse(input)?;
+    let (input, _) = opt(preceded(char('.'), digit1)).parse(input)?;
+    let (input, offset) = opt(parse_utc_offset).parse(input)?;
+    let (input, _) = eof(input)?;
+
+    let timestamp =
+        get_days_since_epoch(year, month, day) * 86_400 + hours * 3600 + minutes * 60 + seconds
+            - offset.unwrap_or_default();
+
+    // Only dates after the Unix epoch are valid timestamps
+    let timestamp = u64::try_from(timestamp).map_err(|_| {
+        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+    })?;
+
+    Ok((input, timestamp))
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
@@ -188,35 +278,6 @@ mod tests {
         }
     }
 
-    #[test_case("\"key\": true", "key", "true", true)]
-    #[test_case("\"key\": true", "key", "false", false)]
-    #[test_case("{key: \"true\"}", "key", "true", false)]
-    #[test_case("key=value", "key", "value", false)]
-    #[test_case(
-        "{\"wrong_key1\": false, \"wrong_key2\": \"value1\"\n\"key\": true\n}",
-        "key",
-        "true",
-        true
-    )]
-    #[test_case(
-        "{\"wrong_key1\": false, \"wrong_key2\": \"value1\"\n\"key\": \"value2\"\n}",
-        "key",
-        "value2",
-        false
-    )]
-    fn test_parse_value_json_unquoted(
-        file_content: &str,
-        key: &str,
-        value: &str,
-        should_pass: bool,
-    ) {
-        if let Ok((_, parsed_value)) = parse_value_json_unquoted(file_content, key) {
-            assert_eq!(value == parsed_value, should_pass);
-        } else {
-            assert!(!should_pass);
-        }
-    }
-
     #[test_case("data:\n\tkey: value", "key", "value", true)]
     #[test_case("data:\n\t\"key\": value", "key", "value", false)]
     #[test_case("key=value", "key", "value", false)]
@@ -265,4 +326,25 @@ mod tests {
             assert!(!should_pass);
         }
     }
+
+    #[test_case("1970-01-01T00:00:00Z", Some(0))]
+    #[test_case("2024-03-10T21:45:30.000Z", Some(1_710_107_130))]
+    #[test_case("2024-02-29T23:59:59.999+01:00", Some(1_709_247_599))]
+    #[test_case("2000-03-01T00:00:00-05:30", Some(951_888_600))]
+    #[test_case("2023-12-25 10:00:00", Some(1_703_498_400))]
+    #[test_case("1969-12-31T23:59:59Z", None; "before epoch")]
+    #[test_case("2024-03-10", None; "date only")]
+    #[test_case("2024-03-10T21:45:30Z trailing", None)]
+    #[test_case("2024-13-45T25:61:61Z", None; "out of range")]
+    #[test_case("2024-00-10T21:45:30Z", None; "month zero")]
+    #[test_case("2023-02-29T21:45:30Z", None; "not a leap year")]
+    #[test_case("2024-03-10T24:00:00Z", None; "hour out of range")]
+    #[test_case("2024-03-10T21:45:60Z", None; "second out of range")]
+    #[test_case("2024-03-10T21:45:30+25:00", None; "offset out of range")]
+    fn test_parse_iso_8601_timestamp(input: &str, expected: Option<u64>) {
+        assert_eq!(
+            parse_iso_8601_timestamp(input).ok().map(|(_, t)| t),
+            expected
+        );
+    }
 }
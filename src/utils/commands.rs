@@ -1,8 +1,11 @@
-use std::process::Command;
+use std::{
+    ffi::{OsStr, OsString},
+    process::Command,
+};
 
 /// Returns a std::process::Command from a given command str and it's arguments
 pub fn get_launch_command<'a>(
-    command: &str,
+    command: impl AsRef<OsStr>,
     args: impl IntoIterator<Item = &'a str>,
     env_vars: impl IntoIterator<Item = (&'a str, &'a str)>,
 ) -> Command {
@@ -12,14 +15,61 @@ pub fn get_launch_command<'a>(
     command
 }
 
+/// Returns a std::process::Command which runs the given flatpak app. The flatpak args are options
+/// for `flatpak run` (e.g. `--command=bottles-cli`), so they are placed before the app ID.
 pub fn get_launch_command_flatpak<'a>(
-    bottle_name: &str,
+    app_id: &str,
     flatpak_args: impl IntoIterator<Item = &'a str>,
     other_args: impl IntoIterator<Item = &'a str>,
     env_vars: impl IntoIterator<Item = (&'a str, &'a str)>,
 ) -> Command {
-    let mut command = get_launch_command("flatpak", flatpak_args, env_vars);
-    command.arg("run").arg(bottle_name).args(other_args);
+    let mut command = get_launch_command("flatpak", ["run"], env_vars);
+    command.args(flatpak_args).arg(app_id).args(other_args);
 
     command
 }
+
+pub fn get_launch_command_snap<'a>(
+    snap_name: &str,
+    other_args: impl IntoIterator<Item = &'a str>,
+    env_vars: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Command {
+    let mut command = get_launch_command("snap", ["run"], env_vars);
+    command.arg(snap_name).args(other_args);
+
+    command
+}
+
+/// Returns a new command which runs the given one on the host via `flatpak-spawn --host`, for use
+/// from inside a flatpak sandbox. The working directory and environment variables of the given
+/// command are passed along.
+pub fn get_launch_command_flatpak_spawn_host(command: &Command) -> Command {
+    let mut host_command = Command::new("flatpak-spawn");
+    host_command.arg("--host");
+
+    if let Some(path) = command.get_current_dir() {
+        let mut arg = OsString::from("--directory=");
+        arg.push(path);
+        host_command.arg(arg);
+    }
+
+    for (key, value) in command.get_envs() {
+        let mut arg = OsString::from(if value.is_some() {
+            "--env="
+        } else {
+            "--unset-env="
+        });
+        arg.push(key);
+        if let Some(value) = value {
+            arg.push("=");
+            arg.push(value);
+        }
+        host_command.arg(arg);
+    }
+
+    host_command
+        .arg(command.get_program())
+        .args(command.get_args());
+
+    host_command
+}
@@ -0,0 +1,585 @@
+//! Parser for Valve's KeyValues text format, used by Steam for `.vdf` and `.acf` files such as
+//! `libraryfolders.vdf`, `localconfig.vdf` and `appmanifest_*.acf`. The binary variant of the
+//! format, used by files such as `shortcuts.vdf`, is parsed by [`parse_binary`].
+//!
+//! ```rust
+//! use lib_game_detector::vdf;
+//!
+//! let key_values = vdf::parse(r#""AppState" { "appid" "620" "name" "Portal 2" }"#).unwrap();
+//! assert_eq!(key_values.get_str_at(&["AppState", "name"]), Some("Portal 2"));
+//! ```
+
+use std::{fs::read_to_string, path::Path, str::FromStr};
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_till, take_while1},
+    character::complete::{char, multispace1, not_line_ending},
+    combinator::{eof, value},
+    error::{Error, ErrorKind},
+    multi::many0_count,
+    sequence::{delimited, preceded},
+};
+
+use crate::error::GamesParsingError;
+
+pub mod appinfo;
+mod binary;
+
+/// Maximum depth of nested `#include` and `#base` directives, to guard against cycles
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// A value in a KeyValues tree: either a string, or a nested block of key/value pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A string value, e.g. `"name" "Portal 2"`. Numbers are also stored as strings.
+    String(String),
+    /// A value of any other type in the binary format, e.g. a 32-bit integer. It is stored as a
+    /// string like [`Value::String`], along with its type so that it is written back unchanged.
+    Typed(String, BinaryType),
+    /// A nested block, e.g. `"AppState" { ... }`.
+    Object(KeyValues),
+}
+
+/// Type of a value in the binary KeyValues format, other than a plain string or a nested block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryType {
+    /// A signed 32-bit integer.
+    Int32,
+    /// A 32-bit floating point number.
+    Float32,
+    /// A pointer, stored as a 32-bit integer.
+    Pointer,
+    /// A UTF-16 string.
+    WideString,
+    /// A color, stored as a 32-bit integer.
+    Color,
+    /// An unsigned 64-bit integer.
+    UInt64,
+    /// A signed 64-bit integer.
+    Int64,
+}
+
+impl Value {
+    /// Returns the string value, or [`None`] if this is a nested block.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::Typed(s, _) => Some(s),
+            Value::Object(_) => None,
+        }
+    }
+
+    /// Returns the nested block, or [`None`] if this is a string.
+    #[must_use]
+    pub fn as_object(&self) -> Option<&KeyValues> {
+        match self {
+            Value::String(_) | Value::Typed(..) => None,
+            Value::Object(o) => Some(o),
+        }
+    }
+
+    /// Parses the string value into any type implementing [`FromStr`], e.g. a number.
+    #[must_use]
+    pub fn parse<T: FromStr>(&self) -> Option<T> {
+        self.as_str().and_then(|s| s.trim().parse().ok())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_owned())
+    }
+}
+
+impl From<KeyValues> for Value {
+    fn from(key_values: KeyValues) -> Self {
+        Value::Object(key_values)
+    }
+}
+
+/// An ordered list of key/value pairs. As in Steam, keys are matched case-insensitively, and the
+/// same key may appear more than once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValues {
+    pairs: Vec<(String, Value)>,
+}
+
+impl KeyValues {
+    /// Returns the value of the first pair with the given key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    /// Returns the values of all pairs with the given key.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Value> {
+        self.pairs
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    /// Returns the string value of the first pair with the given key.
+    #[must_use]
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    /// Returns the nested block of the first pair with the given key.
+    #[must_use]
+    pub fn get_object(&self, key: &str) -> Option<&KeyValues> {
+        self.get(key).and_then(Value::as_object)
+    }
+
+    /// Parses the string value of the first pair with the given key, e.g. into a number.
+    #[must_use]
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key).and_then(Value::parse)
+    }
+
+    /// Follows the given keys through nested blocks, returning the value at the end.
+    #[must_use]
+    pub fn get_at(&self, keys: &[&str]) -> Option<&Value> {
+        let (last, keys) = keys.split_last()?;
+        keys.iter()
+            .try_fold(self, |kv, key| kv.get_object(key))?
+            .get(last)
+    }
+
+    /// Follows the given keys through nested blocks, returning the string value at the end.
+    #[must_use]
+    pub fn get_str_at(&self, keys: &[&str]) -> Option<&str> {
+        self.get_at(keys).and_then(Value::as_str)
+    }
+
+    /// Follows the given keys through nested blocks, returning the nested block at the end.
+    #[must_use]
+    pub fn get_object_at(&self, keys: &[&str]) -> Option<&KeyValues> {
+        self.get_at(keys).and_then(Value::as_object)
+    }
+
+    /// Sets the value of the first pair with the given key, or adds a new pair if there is none.
+    pub fn set(&mut self, key: &str, value: impl Into<Value>) {
+        let value = value.into();
+        match self
+            .pairs
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.pairs.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Returns an iterator over all key/value pairs, in the order they were defined.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Returns the number of key/value pairs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if there are no key/value pairs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns a mutable reference to the nested block of the first pair with the given key.
+    pub fn get_object_mut(&mut self, key: &str) -> Option<&mut KeyValues> {
+        self.pairs
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .and_then(|(_, v)| match v {
+                Value::Object(o) => Some(o),
+                Value::String(_) | Value::Typed(..) => None,
+            })
+    }
+
+    /// Add the pairs from a `#base` file, recursively merging blocks which exist in both and
+    /// skipping any other keys which are already defined.
+    fn merge_base(&mut self, base: KeyValues) {
+        for (key, value) in base.pairs {
+            let existing = self
+                .pairs
+                .iter_mut()
+                .find(|(k, _)| k.eq_ignore_ascii_case(&key));
+
+            match (existing, value) {
+                (Some((_, Value::Object(existing))), Value::Object(base)) => {
+                    existing.merge_base(base)
+                }
+                (Some(_), _) => {}
+                (None, value) => self.pairs.push((key, value)),
+            }
+        }
+    }
+}
+
+// PARSERS ----------------------------------------------------------------------------------------
+/// Skip whitespace and `//` comments
+fn parse_ignored(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        many0_count(alt((multispace1, preceded(tag("//"), not_line_ending)))),
+    )
+    .parse(input)
+}
+
+/// Parse a quoted string, handling the escape sequences `\n`, `\t`, `\\` and `\"`. Any other
+/// backslashes are kept as-is, e.g. in unescaped Windows paths.
+fn parse_quoted(input: &str) -> IResult<&str, String> {
+    let (rest, _) = char('"').parse(input)?;
+    let mut parsed = String::new();
+    let mut chars = rest.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((&rest[i + 1..], parsed)),
+            '\\' => match chars.next() {
+                Some((_, 'n')) => parsed.push('\n'),
+                Some((_, 't')) => parsed.push('\t'),
+                Some((_, c @ ('\\' | '"'))) => parsed.push(c),
+                Some((_, c)) => {
+                    parsed.push('\\');
+                    parsed.push(c);
+                }
+                None => break,
+            },
+            c => parsed.push(c),
+        }
+    }
+
+    Err(nom::Err::Error(Error::new(input, ErrorKind::Char)))
+}
+
+/// Parse an unquoted string, which ends at whitespace or any of the special characters
+fn parse_unquoted(input: &str) -> IResult<&str, String> {
+    take_while1(|c: char| !c.is_whitespace() && !matches!(c, '"' | '{' | '}' | '['))
+        .map(str::to_owned)
+        .parse(input)
+}
+
+fn parse_string(input: &str) -> IResult<&str, String> {
+    alt((parse_quoted, parse_unquoted)).parse(input)
+}
+
+/// Skip a conditional such as `[$WIN32]`, which are ignored so that every value is kept
+fn parse_conditional(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        preceded(
+            parse_ignored,
+            delimited(char('['), take_till(|c| c == ']'), char(']')),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_object(input: &str) -> IResult<&str, KeyValues> {
+    delimited(char('{'), parse_pairs, preceded(parse_ignored, char('}'))).parse(input)
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (String, Value)> {
+    let (input, key) = preceded(parse_ignored, parse_string).parse(input)?;
+    let (input, _) = parse_conditional.parse(input).unwrap_or((input, ()));
+    let (input, value) = preceded(
+        parse_ignored,
+        alt((
+            parse_object.map(Value::Object),
+            parse_string.map(Value::String),
+        )),
+    )
+    .parse(input)?;
+    let (input, _) = parse_conditional.parse(input).unwrap_or((input, ()));
+
+    Ok((input, (key, value)))
+}
+
+fn parse_pairs(mut input: &str) -> IResult<&str, KeyValues> {
+    let mut key_values = KeyValues::default();
+
+    loop {
+        let (rest, _) = parse_ignored(input)?;
+        if rest.is_empty() || rest.starts_with('}') {
+            return Ok((rest, key_values));
+        }
+
+        let (rest, pair) = parse_pair(rest)?;
+        key_values.pairs.push(pair);
+        input = rest;
+    }
+}
+
+/// Parse the contents of a KeyValues file. `#include` and `#base` directives are kept as regular
+/// pairs, use [`parse_file`] to resolve them.
+pub fn parse(input: &str) -> Result<KeyValues, GamesParsingError> {
+    let (input, key_values) = parse_pairs(input)?;
+    parse_ignored.and(eof).parse(input)?;
+
+    Ok(key_values)
+}
+
+/// Parse the contents of a binary KeyValues file, such as `shortcuts.vdf`. Values which aren't
+/// plain strings, e.g. integers, are given as [`Value::Typed`], and can be read as strings like
+/// all other values.
+pub fn parse_binary(input: &[u8]) -> Result<KeyValues, GamesParsingError> {
+    binary::parse_binary_object(input, None)
+        .map(|(_, key_values)| key_values)
+        .map_err(binary::map_binary_err)
+}
+
+/// Serialize key values into the binary KeyValues format, e.g. to write `shortcuts.vdf`.
+/// [`Value::Typed`] values are written with the type they were parsed with. Of the plain string
+/// values, e.g. those which were added, the values of the given keys (matched case-insensitively)
+/// are written as 32-bit integers, and all other values as strings.
+#[must_use]
+pub fn to_binary(key_values: &KeyValues, int_keys: &[&str]) -> Vec<u8> {
+    let mut output = vec![];
+    binary::write_binary_object(&mut output, key_values, &|key: &str| {
+        int_keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+    });
+
+    output
+}
+
+/// Read and parse a KeyValues file, resolving any top-level `#include` and `#base` directives
+/// relative to the file's directory.
+pub fn parse_file(path: &Path) -> Result<KeyValues, GamesParsingError> {
+    parse_file_with_depth(path, 0)
+}
+
+fn parse_file_with_depth(path: &Path, depth: usize) -> Result<KeyValues, GamesParsingError> {
+    let parsed = parse(&read_to_string(path)?)?;
+
+    let mut key_values = KeyValues::default();
+    let mut bases = Vec::new();
+
+    for (key, value) in parsed.pairs {
+        let directive = key.to_ascii_lowercase();
+        let (Some(file_name), "#include" | "#base") = (value.as_str(), directive.as_str()) else {
+            key_values.pairs.push((key, value));
+            continue;
+        };
+
+        if depth >= MAX_INCLUDE_DEPTH {
+            return Err(GamesParsingError::Other(format!(
+                "Too many nested includes in {path:?}"
+            )));
+        }
+
+        let path_included = path.with_file_name(file_name);
+        let included = parse_file_with_depth(&path_included, depth + 1)?;
+        if directive == "#include" {
+            key_values.pairs.extend(included.pairs);
+        } else {
+            bases.push(included);
+        }
+    }
+
+    for base in bases {
+        key_values.merge_base(base);
+    }
+
+    Ok(key_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test_case(r#""key" "value""#, "value"; "quoted")]
+    #[test_case("key value", "value"; "unquoted")]
+    #[test_case(r#""key" "say \"hi\"""#, "say \"hi\""; "escaped quotes")]
+    #[test_case(r#""key" "a\\b\nc""#, "a\\b\nc"; "escaped backslash and newline")]
+    #[test_case(r#""key" "C:\Games""#, "C:\\Games"; "unknown escape kept")]
+    #[test_case("// comment\n\"key\" \"value\" // trailing", "value"; "comments")]
+    #[test_case(r#""key" "value" [$WIN32]"#, "value"; "conditional")]
+    #[test_case(r#""KEY" "value""#, "value"; "case insensitive")]
+    #[test_case(r#""key" """#, ""; "empty")]
+    fn test_parse_value(input: &str, expected: &str) -> Result<(), GamesParsingError> {
+        let key_values = parse(input)?;
+        assert_eq!(key_values.get_str("key"), Some(expected));
+
+        Ok(())
+    }
+
+    #[test_case(r#""key" "value"#; "unclosed quote")]
+    #[test_case(r#""key" { "a" "b""#; "unclosed block")]
+    #[test_case(r#""key" "value" }"#; "unexpected closing brace")]
+    #[test_case(r#""key""#; "missing value")]
+    fn test_parse_invalid(input: &str) {
+        assert!(parse(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_nested() -> Result<(), GamesParsingError> {
+        let key_values = parse(
+            r#"
+            "libraryfolders"
+            {
+                "0"
+                {
+                    "path"      "/path/to/library"
+                    "apps" { "228980" "399788250" }
+                }
+                "1" [$LINUX]
+                {
+                    "path"      "/path/to/\"other\" library"
+                    "apps" {}
+                }
+            }
+            "#,
+        )?;
+
+        let libraries = key_values
+            .get_object("libraryfolders")
+            .expect("Library folders should be parsed");
+        assert_eq!(libraries.len(), 2);
+
+        assert_eq!(
+            key_values.get_str_at(&["libraryfolders", "0", "path"]),
+            Some("/path/to/library")
+        );
+        assert_eq!(
+            key_values.get_str_at(&["libraryfolders", "1", "path"]),
+            Some("/path/to/\"other\" library")
+        );
+        assert_eq!(
+            key_values
+                .get_object_at(&["libraryfolders", "0", "apps"])
+                .and_then(|apps| apps.get_parsed::<u64>("228980")),
+            Some(399788250)
+        );
+        assert!(
+            key_values
+                .get_object_at(&["libraryfolders", "1", "apps"])
+                .is_some_and(KeyValues::is_empty)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_file_directives() -> Result<(), GamesParsingError> {
+        let key_values = parse_file(&get_mock_file_system_path().join("vdf/main.vdf"))?;
+
+        let root = key_values
+            .get_object("root")
+            .expect("Root should be parsed");
+        // Defined in main.vdf, so not overridden by the base file
+        assert_eq!(root.get_str("name"), Some("main"));
+        // Only defined in the base file
+        assert_eq!(root.get_str("from_base"), Some("base"));
+        // Pairs from included files are added as they are
+        assert_eq!(key_values.get_str_at(&["included", "key"]), Some("value"));
+        assert!(key_values.get("#include").is_none());
+        assert!(key_values.get("#base").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_binary() -> Result<(), GamesParsingError> {
+        let input = [
+            b"\x00shortcuts\x00".as_slice(),
+            b"\x000\x00",
+            b"\x01AppName\x00Game\x00",
+            // Integers starting with a `0x01` byte mustn't be confused with strings
+            b"\x02IsHidden\x00\x01\x00\x00\x00",
+            b"\x02appid\x00\x01\x5e\xd0\xb2",
+            b"\x00tags\x00\x010\x00favorite\x00\x08",
+            b"\x08\x08\x08",
+        ]
+        .concat();
+        let key_values = parse_binary(&input)?;
+
+        let shortcut = key_values
+            .get_object_at(&["shortcuts", "0"])
+            .expect("Shortcut should be parsed");
+        assert_eq!(shortcut.get_str("appname"), Some("Game"));
+        assert_eq!(shortcut.get_str("IsHidden"), Some("1"));
+        assert_eq!(
+            shortcut.get_parsed::<i32>("appid"),
+            Some(3000000001_u32 as i32)
+        );
+        assert_eq!(shortcut.get_str_at(&["tags", "0"]), Some("favorite"));
+
+        assert!(parse_binary(b"\x02truncated\x00\x01").is_err());
+
+        // Written back exactly as it was, given which keys hold integers
+        assert_eq!(to_binary(&key_values, &["isHidden", "AppID"]), input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_types() -> Result<(), GamesParsingError> {
+        let input = [
+            b"\x00shortcuts\x00".as_slice(),
+            b"\x000\x00",
+            b"\x02UnknownInt\x00\xff\xff\xff\xff",
+            b"\x07SteamID\x00\x62\x03\x00\x00\x01\x00\x10\x01",
+            b"\x03Scale\x00\x00\x00\xc0\x3f",
+            b"\x0aOffset\x00\xfe\xff\xff\xff\xff\xff\xff\xff",
+            b"\x06Color\x00\x00\x80\xff\x00",
+            b"\x05Wide\x00W\x00i\x00d\x00e\x00\x00\x00",
+            b"\x08\x08\x08",
+        ]
+        .concat();
+        let key_values = parse_binary(&input)?;
+
+        let shortcut = key_values
+            .get_object_at(&["shortcuts", "0"])
+            .expect("Shortcut should be parsed");
+        assert_eq!(
+            shortcut.get("UnknownInt"),
+            Some(&Value::Typed(String::from("-1"), BinaryType::Int32))
+        );
+        assert_eq!(
+            shortcut.get_parsed::<u64>("SteamID"),
+            Some(76561197960266594)
+        );
+        assert_eq!(shortcut.get_parsed::<f32>("Scale"), Some(1.5));
+        assert_eq!(shortcut.get_parsed::<i64>("Offset"), Some(-2));
+        assert_eq!(shortcut.get_str("Wide"), Some("Wide"));
+
+        // Each value keeps its type, even for keys which aren't given as integers
+        assert_eq!(to_binary(&key_values, &[]), input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set() {
+        let mut key_values = KeyValues::default();
+        key_values.set("Name", "old");
+        key_values.set("name", "new");
+        key_values.set("block", KeyValues::default());
+
+        assert_eq!(key_values.len(), 2);
+        assert_eq!(key_values.get_str("NAME"), Some("new"));
+        assert!(
+            key_values
+                .get_object_mut("block")
+                .is_some_and(|b| b.is_empty())
+        );
+    }
+}
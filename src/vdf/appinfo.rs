@@ -0,0 +1,379 @@
+//! Parser for Steam's binary `appcache/appinfo.vdf` file, which caches information about every
+//! app known to the Steam client, such as its type, developer and supported operating systems.
+//!
+//! Only the formats used by current versions of Steam (v28 and v29) are supported.
+//!
+//! ```rust,no_run
+//! use std::path::Path;
+//!
+//! use lib_game_detector::vdf::appinfo;
+//!
+//! let apps = appinfo::parse_file(Path::new("appcache/appinfo.vdf"), |app_id| app_id == 620).unwrap();
+//! if let Some(app) = apps.get(&620) {
+//!     println!("{:?} is a {:?}", app.name, app.app_type);
+//! }
+//! ```
+
+use std::{collections::HashMap, fs::read, path::Path};
+
+use nom::{
+    IResult, Parser,
+    bytes::complete::take,
+    multi::count,
+    number::complete::{le_i64, le_u32},
+};
+use tracing::{trace, warn};
+
+use super::{
+    KeyValues,
+    binary::{map_binary_err, parse_binary_object, parse_c_string},
+};
+use crate::{data::GameMetadata, error::GamesParsingError};
+
+/// Magic number at the start of v28 files
+const MAGIC_V28: u32 = 0x07_56_44_28;
+/// Magic number at the start of v29 files, which store keys in a string table at the end of the
+/// file instead of inline
+const MAGIC_V29: u32 = 0x07_56_44_29;
+/// Size of the data preceding each app's key values: info state, last updated, PICS token, text
+/// SHA-1, change number and binary SHA-1
+const APP_HEADER_SIZE: usize = 4 + 4 + 8 + 20 + 4 + 20;
+
+/// Names of the genre IDs used by Steam
+const GENRES: [(u32, &str); 26] = [
+    (1, "Action"),
+    (2, "Strategy"),
+    (3, "RPG"),
+    (4, "Casual"),
+    (9, "Racing"),
+    (18, "Sports"),
+    (23, "Indie"),
+    (25, "Adventure"),
+    (28, "Simulation"),
+    (29, "Massively Multiplayer"),
+    (37, "Free to Play"),
+    (51, "Animation & Modeling"),
+    (52, "Audio Production"),
+    (53, "Design & Illustration"),
+    (54, "Education"),
+    (55, "Photo Editing"),
+    (56, "Software Training"),
+    (57, "Utilities"),
+    (58, "Video Production"),
+    (59, "Web Publishing"),
+    (60, "Game Development"),
+    (70, "Early Access"),
+    (71, "Sexual Content"),
+    (72, "Nudity"),
+    (73, "Violent"),
+    (74, "Gore"),
+];
+
+/// Type of a Steam app, as given by `common/type` in `appinfo.vdf`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppType {
+    /// A game
+    Game,
+    /// A demo of a game
+    Demo,
+    /// A mod of another game, e.g. Black Mesa before its full release
+    Mod,
+    /// Downloadable content for another app
+    Dlc,
+    /// A tool such as Proton, the Steam Linux Runtime or a dedicated server
+    Tool,
+    /// Software which isn't a game
+    Application,
+    /// A soundtrack
+    Music,
+    /// A video
+    Video,
+    /// Shared configuration, e.g. Steamworks Common Redistributables
+    Config,
+    /// Any other type, in lowercase
+    Other(String),
+}
+
+impl AppType {
+    /// Returns `true` if apps of this type are playable games (including demos and mods).
+    #[must_use]
+    pub fn is_game(&self) -> bool {
+        matches!(self, AppType::Game | AppType::Demo | AppType::Mod)
+    }
+}
+
+impl From<&str> for AppType {
+    fn from(app_type: &str) -> Self {
+        match app_type.to_lowercase().as_str() {
+            "game" => AppType::Game,
+            "demo" => AppType::Demo,
+            "mod" => AppType::Mod,
+            "dlc" => AppType::Dlc,
+            "tool" => AppType::Tool,
+            "application" => AppType::Application,
+            "music" => AppType::Music,
+            "video" => AppType::Video,
+            "config" => AppType::Config,
+            other => AppType::Other(other.to_owned()),
+        }
+    }
+}
+
+/// Information about a single Steam app from `appinfo.vdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppInfo {
+    /// Steam app ID.
+    pub app_id: u32,
+    /// Type of the app.
+    pub app_type: AppType,
+    /// Name of the app, e.g. `Portal 2`.
+    pub name: Option<String>,
+    /// App ID of the app this belongs to, e.g. the base game of DLC.
+    pub parent_app_id: Option<u32>,
+    /// Developer, publisher, genres etc. of the app.
+    pub metadata: GameMetadata,
+    /// All key values stored for the app, under the `appinfo` root block.
+    pub key_values: KeyValues,
+}
+
+impl AppInfo {
+    fn new(app_id: u32, key_values: KeyValues) -> Self {
+        let common = key_values.get_object_at(&["appinfo", "common"]);
+        let get_common = |key: &str| common.and_then(|c| c.get_str(key));
+
+        // Newer entries list developers and publishers under `associations`, older ones only have
+        // them under `extended`
+        let get_association = |association_type: &str| {
+            let names = common
+                .and_then(|c| c.get_object("associations"))
+                .into_iter()
+                .flat_map(KeyValues::iter)
+                .filter_map(|(_, a)| a.as_object())
+                .filter(|a| a.get_str("type") == Some(association_type))
+                .filter_map(|a| a.get_str("name"))
+                .collect::<Vec<_>>();
+
+            if names.is_empty() {
+                key_values
+                    .get_str_at(&["appinfo", "extended", association_type])
+                    .filter(|n| !n.is_empty())
+                    .map(str::to_owned)
+            } else {
+                Some(names.join(", "))
+            }
+        };
+
+        let release_date = ["steam_release_date", "original_release_date"]
+            .into_iter()
+            .find_map(|key| common.and_then(|c| c.get_parsed::<u64>(key)));
+
+        let genres = common
+            .and_then(|c| c.get_object("genres"))
+            .into_iter()
+            .flat_map(KeyValues::iter)
+            .filter_map(|(_, id)| id.parse::<u32>())
+            .filter_map(|id| GENRES.iter().find(|(i, _)| *i == id))
+            .map(|(_, genre)| (*genre).to_owned())
+            .collect();
+
+        let supported_os = get_common("oslist")
+            .map(|os_list| {
+                os_list
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|os| !os.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let metadata = GameMetadata {
+            developer: get_association("developer"),
+            publisher: get_association("publisher"),
+            release_date,
+            genres,
+            supported_os,
+            // Everything else is only known from the app's manifest or the user's config
+            ..Default::default()
+        };
+
+        AppInfo {
+            app_id,
+            app_type: get_common("type").map_or(AppType::Other(String::new()), AppType::from),
+            name: get_common("name").map(str::to_owned),
+            parent_app_id: get_common("parent").and_then(|p| p.parse().ok()),
+            metadata,
+            key_values,
+        }
+    }
+}
+
+// PARSERS ------------------------------------------------------------------------------
+fn parse_string_table(input: &[u8]) -> IResult<&[u8], Vec<String>> {
+    let (input, len) = le_u32(input)?;
+    count(parse_c_string, len as usize).parse(input)
+}
+
+/// Parses the next app's ID and data, returning [`None`] once the end of the apps is reached
+fn parse_app_entry(input: &[u8]) -> IResult<&[u8], Option<(u32, &[u8])>> {
+    let (input, app_id) = le_u32(input)?;
+    if app_id == 0 {
+        return Ok((input, None));
+    }
+
+    let (input, size) = le_u32(input)?;
+    let (input, data) = take(size as usize).parse(input)?;
+
+    Ok((input, Some((app_id, data))))
+}
+
+/// Parse the contents of an `appinfo.vdf` file, keeping only apps for which `filter` returns
+/// `true`. Skipping apps is cheap, so a filter should be used when only a few apps are needed.
+pub fn parse(
+    input: &[u8],
+    mut filter: impl FnMut(u32) -> bool,
+) -> Result<HashMap<u32, AppInfo>, GamesParsingError> {
+    let (mut rest, (magic, _universe)) = (le_u32, le_u32).parse(input).map_err(map_binary_err)?;
+
+    let string_table = match magic {
+        MAGIC_V28 => None,
+        MAGIC_V29 => {
+            let (r, offset) = le_i64(rest).map_err(map_binary_err)?;
+            rest = r;
+
+            let table_input = usize::try_from(offset)
+                .ok()
+                .and_then(|offset| input.get(offset..))
+                .ok_or_else(|| {
+                    GamesParsingError::Other(format!(
+                        "Invalid appinfo.vdf string table offset: {offset}"
+                    ))
+                })?;
+            Some(parse_string_table(table_input).map_err(map_binary_err)?.1)
+        }
+        _ => {
+            return Err(GamesParsingError::Other(format!(
+                "Unsupported appinfo.vdf version: {magic:#010x}"
+            )));
+        }
+    };
+
+    let mut apps = HashMap::new();
+    while let (r, Some((app_id, data))) = parse_app_entry(rest).map_err(map_binary_err)? {
+        rest = r;
+
+        if !filter(app_id) {
+            continue;
+        }
+
+        let Some(data) = data.get(APP_HEADER_SIZE..) else {
+            warn!("Skipped app {app_id} in appinfo.vdf as its data is truncated");
+            continue;
+        };
+
+        match parse_binary_object(data, string_table.as_deref()) {
+            Ok((_, key_values)) => {
+                trace!("Parsed app {app_id} from appinfo.vdf");
+                apps.insert(app_id, AppInfo::new(app_id, key_values));
+            }
+            Err(e) => warn!("Skipped app {app_id} in appinfo.vdf: {}", map_binary_err(e)),
+        }
+    }
+
+    Ok(apps)
+}
+
+/// Read and parse the `appinfo.vdf` file at the given path. See [`parse`].
+pub fn parse_file(
+    path: &Path,
+    filter: impl FnMut(u32) -> bool,
+) -> Result<HashMap<u32, AppInfo>, GamesParsingError> {
+    parse(&read(path)?, filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::linux::test_utils::get_mock_file_system_path;
+
+    #[test_case("appinfo_v28.vdf"; "v28")]
+    #[test_case("appinfo_v29.vdf"; "v29")]
+    fn test_parse_appinfo(filename: &str) -> Result<(), GamesParsingError> {
+        let path = get_mock_file_system_path().join("vdf").join(filename);
+        let apps = parse_file(&path, |_| true)?;
+
+        assert_eq!(apps.len(), 3);
+
+        let terraria = &apps[&105600];
+        assert_eq!(terraria.app_id, 105600);
+        assert_eq!(terraria.app_type, AppType::Game);
+        assert_eq!(terraria.name.as_deref(), Some("Terraria"));
+        assert_eq!(
+            terraria.metadata,
+            GameMetadata {
+                developer: Some(String::from("Re-Logic")),
+                publisher: Some(String::from("Re-Logic")),
+                release_date: Some(1305529200),
+                genres: vec![
+                    String::from("Action"),
+                    String::from("Indie"),
+                    String::from("Adventure")
+                ],
+                supported_os: vec![
+                    String::from("windows"),
+                    String::from("macos"),
+                    String::from("linux")
+                ],
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            terraria
+                .key_values
+                .get_str_at(&["appinfo", "extended", "homepage"]),
+            Some("https://terraria.org")
+        );
+
+        let proton = &apps[&1493710];
+        assert_eq!(proton.app_type, AppType::Tool);
+        assert!(!proton.app_type.is_game());
+        assert!(proton.metadata.developer.is_none());
+
+        // Developer and publisher fall back to the `extended` block
+        let portal = &apps[&620];
+        assert!(portal.app_type.is_game());
+        assert_eq!(portal.metadata.developer.as_deref(), Some("Valve"));
+        assert_eq!(portal.metadata.publisher.as_deref(), Some("Valve"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_appinfo_filter() -> Result<(), GamesParsingError> {
+        let path = get_mock_file_system_path().join("vdf/appinfo_v29.vdf");
+        let apps = parse_file(&path, |app_id| app_id == 620)?;
+
+        assert_eq!(apps.len(), 1);
+        assert!(apps.contains_key(&620));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_appinfo_invalid() {
+        assert!(parse(&[0x27, 0x44, 0x56, 0x07, 1, 0, 0, 0], |_| true).is_err());
+        assert!(parse(&[0x29, 0x44, 0x56, 0x07, 1, 0, 0, 0], |_| true).is_err());
+        assert!(parse(&[], |_| true).is_err());
+    }
+
+    #[test]
+    fn test_app_type() {
+        assert_eq!(AppType::from("Game"), AppType::Game);
+        assert_eq!(AppType::from("DLC"), AppType::Dlc);
+        assert_eq!(AppType::from("Beta"), AppType::Other(String::from("beta")));
+        assert!(AppType::from("demo").is_game());
+        assert!(!AppType::from("application").is_game());
+    }
+}
@@ -0,0 +1,189 @@
+//! Parser and writer for Valve's binary KeyValues format, used by files such as `shortcuts.vdf`
+//! and `appcache/appinfo.vdf`.
+
+use nom::{
+    IResult, Parser,
+    bytes::complete::{tag, take_until},
+    error::{Error, ErrorKind},
+    number::complete::{le_f32, le_i32, le_i64, le_u8, le_u16, le_u32, le_u64},
+    sequence::terminated,
+};
+
+use super::{BinaryType, KeyValues, Value};
+use crate::error::GamesParsingError;
+
+// Binary KeyValues types
+const TYPE_OBJECT: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WIDE_STRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+const TYPE_END_ALT: u8 = 0x0B;
+
+// PARSERS ------------------------------------------------------------------------------
+/// Keys of a v29 `appinfo.vdf` file, which are stored in a string table rather than inline
+pub(super) type StringTable<'a> = Option<&'a [String]>;
+
+pub(super) fn parse_c_string(input: &[u8]) -> IResult<&[u8], String> {
+    terminated(take_until(&[0][..]), tag(&[0][..]))
+        .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+        .parse(input)
+}
+
+fn parse_wide_string(mut input: &[u8]) -> IResult<&[u8], String> {
+    let mut chars = vec![];
+    loop {
+        let (rest, c) = le_u16(input)?;
+        input = rest;
+        if c == 0 {
+            return Ok((input, String::from_utf16_lossy(&chars)));
+        }
+        chars.push(c);
+    }
+}
+
+fn parse_key<'a>(input: &'a [u8], string_table: StringTable) -> IResult<&'a [u8], String> {
+    let Some(string_table) = string_table else {
+        return parse_c_string(input);
+    };
+
+    let (rest, index) = le_u32(input)?;
+    match string_table.get(index as usize) {
+        Some(key) => Ok((rest, key.clone())),
+        None => Err(nom::Err::Failure(Error::new(input, ErrorKind::Verify))),
+    }
+}
+
+pub(super) fn parse_binary_object<'a>(
+    mut input: &'a [u8],
+    string_table: StringTable,
+) -> IResult<&'a [u8], KeyValues> {
+    let mut pairs = vec![];
+
+    loop {
+        let (rest, value_type) = le_u8(input)?;
+        if matches!(value_type, TYPE_END | TYPE_END_ALT) {
+            return Ok((rest, KeyValues { pairs }));
+        }
+
+        let (rest, key) = parse_key(rest, string_table)?;
+        let (rest, value) = match value_type {
+            TYPE_OBJECT => {
+                let (rest, object) = parse_binary_object(rest, string_table)?;
+                (rest, Value::Object(object))
+            }
+            TYPE_STRING => parse_c_string.map(Value::String).parse(rest)?,
+            TYPE_WIDE_STRING => parse_wide_string
+                .map(|s| Value::Typed(s, BinaryType::WideString))
+                .parse(rest)?,
+            TYPE_INT32 | TYPE_POINTER | TYPE_COLOR => {
+                let binary_type = match value_type {
+                    TYPE_INT32 => BinaryType::Int32,
+                    TYPE_POINTER => BinaryType::Pointer,
+                    _ => BinaryType::Color,
+                };
+                le_i32
+                    .map(|n| Value::Typed(n.to_string(), binary_type))
+                    .parse(rest)?
+            }
+            TYPE_FLOAT32 => le_f32
+                .map(|n| Value::Typed(n.to_string(), BinaryType::Float32))
+                .parse(rest)?,
+            TYPE_UINT64 => le_u64
+                .map(|n| Value::Typed(n.to_string(), BinaryType::UInt64))
+                .parse(rest)?,
+            TYPE_INT64 => le_i64
+                .map(|n| Value::Typed(n.to_string(), BinaryType::Int64))
+                .parse(rest)?,
+            _ => return Err(nom::Err::Failure(Error::new(input, ErrorKind::Switch))),
+        };
+
+        pairs.push((key, value));
+        input = rest;
+    }
+}
+
+pub(super) fn map_binary_err(err: nom::Err<Error<&[u8]>>) -> GamesParsingError {
+    err.map_input(|input| format!("<{} bytes>", input.len()))
+        .into()
+}
+
+// WRITERS ------------------------------------------------------------------------------
+fn write_c_string(output: &mut Vec<u8>, s: &str) {
+    output.extend_from_slice(s.as_bytes());
+    output.push(0);
+}
+
+/// Get the type and encoded bytes of a value with the given binary type, or [`None`] if the value
+/// isn't valid for that type
+fn encode_typed(s: &str, binary_type: BinaryType) -> Option<(u8, Vec<u8>)> {
+    let encoded = match binary_type {
+        BinaryType::Int32 => (TYPE_INT32, s.parse::<i32>().ok()?.to_le_bytes().to_vec()),
+        BinaryType::Float32 => (TYPE_FLOAT32, s.parse::<f32>().ok()?.to_le_bytes().to_vec()),
+        BinaryType::Pointer => (TYPE_POINTER, s.parse::<i32>().ok()?.to_le_bytes().to_vec()),
+        BinaryType::Color => (TYPE_COLOR, s.parse::<i32>().ok()?.to_le_bytes().to_vec()),
+        BinaryType::UInt64 => (TYPE_UINT64, s.parse::<u64>().ok()?.to_le_bytes().to_vec()),
+        BinaryType::Int64 => (TYPE_INT64, s.parse::<i64>().ok()?.to_le_bytes().to_vec()),
+        BinaryType::WideString => (
+            TYPE_WIDE_STRING,
+            s.encode_utf16()
+                .chain([0])
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+        ),
+    };
+
+    Some(encoded)
+}
+
+/// Get the type and encoded bytes of a plain string value, which is written as a 32-bit integer
+/// if `is_int` is `true` and it is a valid integer
+fn encode_string(s: &str, is_int: bool) -> (u8, Vec<u8>) {
+    // Unsigned values, such as app IDs, use the full 32 bits
+    let int = is_int
+        .then(|| {
+            s.parse::<i32>()
+                .or_else(|_| s.parse::<u32>().map(|n| n as i32))
+        })
+        .and_then(Result::ok);
+
+    match int {
+        Some(n) => (TYPE_INT32, n.to_le_bytes().to_vec()),
+        None => (TYPE_STRING, [s.as_bytes(), &[0]].concat()),
+    }
+}
+
+/// Write the given key values, followed by the end marker of their block. Typed values are written
+/// with their own type. Plain string values of keys for which `is_int` returns `true` are written
+/// as 32-bit integers, and all other plain string values are written as strings.
+pub(super) fn write_binary_object(
+    output: &mut Vec<u8>,
+    key_values: &KeyValues,
+    is_int: &impl Fn(&str) -> bool,
+) {
+    for (key, value) in &key_values.pairs {
+        let (value_type, bytes) = match value {
+            Value::Object(object) => {
+                output.push(TYPE_OBJECT);
+                write_c_string(output, key);
+                write_binary_object(output, object, is_int);
+                continue;
+            }
+            Value::Typed(s, binary_type) => {
+                encode_typed(s, *binary_type).unwrap_or_else(|| encode_string(s, is_int(key)))
+            }
+            Value::String(s) => encode_string(s, is_int(key)),
+        };
+
+        output.push(value_type);
+        write_c_string(output, key);
+        output.extend_from_slice(&bytes);
+    }
+
+    output.push(TYPE_END);
+}